@@ -1,20 +1,74 @@
+use alloc::collections::BTreeSet;
+
 use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::AccountHash, Key, URef};
+use types::{Key, URef};
 
 use crate::utils;
 
 use super::ContractError;
 
 pub const ADMINS_DICT: &str = "account-info-admins";
+pub const ROLES_DICT: &str = "account-info-roles";
+pub const ROLE_COUNTS_DICT: &str = "account-info-role-counts";
+pub const ADMIN_LIST_DICT: &str = "account-info-admin-list";
+pub const ADMIN_LIST_INDEX_DICT: &str = "account-info-admin-list-index";
+pub const ADMIN_LIST_COUNT: &str = "admin_list_count";
+pub const MAX_ADMINS_PAGE_SIZE: u32 = 50;
+pub const NOMINATIONS_DICT: &str = "account-info-admin-nominations";
+pub const EXPIRY_DICT: &str = "account-info-admin-expiry";
+/// Key of the number of super admins without an expiry in the role counts dictionary.
+pub const PERMANENT_SUPER_ADMINS: &str = "permanent";
+/// Stored instead of the expiry block time of grants that don't expire.
+pub const NO_EXPIRY: u64 = 0;
+/// Time a nominated account has to accept the nomination, in milliseconds.
+pub const NOMINATION_PERIOD: u64 = 7 * 24 * 60 * 60 * 1000;
+/// Number of admins stored by the contract versions without roles.
 pub const ADMINS_COUNT: &str = "admins_count";
+/// Installer argument with the admins added before the admin list was introduced.
+pub const LEGACY_ADMINS: &str = "legacy_admins";
 pub const ADMIN_ACTIVE: bool = true;
 pub const ADMIN_DISABLED: bool = false;
 
+/// Administrative roles. The roles of an account are stored as a combination of these bit flags.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    /// Manages the admins, their roles and the URL policies. Can call every admin entry point.
+    SuperAdmin = 1,
+    /// Sets and deletes the URLs of other accounts and manages the domain blocklist.
+    Moderator = 2,
+    /// Manages the fees.
+    FeeManager = 4,
+}
+
+pub const ALL_ROLES: [Role; 3] = [Role::SuperAdmin, Role::Moderator, Role::FeeManager];
+
+impl Role {
+    pub fn from_u8(value: u8) -> Option<Role> {
+        ALL_ROLES.iter().copied().find(|role| *role as u8 == value)
+    }
+}
+
+/// Admin accounts and their roles. Contract packages, e.g. multisig or governance contracts,
+/// can be admins as well, so admins are identified by a `Key`.
+/// The `account-info-admins` dictionary keeps telling whether an account has any role,
+/// the roles themselves are stored in the `account-info-roles` dictionary.
+/// Every account that has been granted a role is also appended to the admin list,
+/// stored under the position in the list, as dictionaries can't be iterated.
+/// Accounts without roles are nominated first, and only get the roles once they accept
+/// the nomination, so a mistyped account hash can't take an admin slot.
+/// Admins can be added with an expiry, after which they have no roles. There is always
+/// at least one super admin without an expiry, so the contract can't end up without admins.
 pub struct Admins {
     dict_uref: URef,
+    roles_dict_uref: URef,
+    role_counts_dict_uref: URef,
+    list_dict_uref: URef,
+    list_index_dict_uref: URef,
+    nominations_dict_uref: URef,
+    expiry_dict_uref: URef,
 }
 
 impl Admins {
@@ -23,50 +77,345 @@ impl Admins {
         let dict_uref: &URef = dict_key.as_uref().unwrap_or_revert();
         Admins {
             dict_uref: *dict_uref,
+            roles_dict_uref: utils::get_dictionary(ROLES_DICT),
+            role_counts_dict_uref: utils::get_dictionary(ROLE_COUNTS_DICT),
+            list_dict_uref: utils::get_dictionary(ADMIN_LIST_DICT),
+            list_index_dict_uref: utils::get_dictionary(ADMIN_LIST_INDEX_DICT),
+            nominations_dict_uref: utils::get_dictionary(NOMINATIONS_DICT),
+            expiry_dict_uref: utils::get_dictionary(EXPIRY_DICT),
         }
     }
 
-    pub fn is_admin(&self, account: &AccountHash) -> bool {
-        let result: Option<bool> =
-            storage::dictionary_get(self.dict_uref, &account.to_string()).unwrap_or_revert();
-        match result {
-            Some(value) => value == ADMIN_ACTIVE,
-            None => false,
+    /// Returns the role flags of `account`, or zero if its grant has expired.
+    pub fn roles(&self, account: &Key) -> u8 {
+        if self.is_expired(account) {
+            0
+        } else {
+            self.stored_roles(account)
+        }
+    }
+
+    /// Returns the block time at which the roles of `account` expire, if they do.
+    pub fn expires_at(&self, account: &Key) -> Option<u64> {
+        let expires_at: Option<u64> =
+            storage::dictionary_get(self.expiry_dict_uref, &utils::identity_key(account))
+                .unwrap_or_revert();
+        expires_at.filter(|expires_at| *expires_at != NO_EXPIRY)
+    }
+
+    pub fn is_admin(&self, account: &Key) -> bool {
+        self.roles(account) != 0
+    }
+
+    /// Whether `account` has `role`. Super admins have every role.
+    pub fn has_role(&self, account: &Key, role: Role) -> bool {
+        self.roles(account) & (role as u8 | Role::SuperAdmin as u8) != 0
+    }
+
+    /// Number of accounts with `role`.
+    pub fn role_count(&self, role: Role) -> u32 {
+        let count: Option<u32> =
+            storage::dictionary_get(self.role_counts_dict_uref, &role_key(role)).unwrap_or_revert();
+        match (count, role) {
+            (Some(count), _) => count,
+            // Admins added before roles were introduced are super admins.
+            (None, Role::SuperAdmin) => utils::get_key(ADMINS_COUNT).unwrap_or_default(),
+            (None, _) => 0,
         }
     }
 
-    pub fn add(&self, account: &AccountHash) {
+    /// Number of super admins without an expiry.
+    pub fn permanent_super_admins(&self) -> u32 {
+        let count: Option<u32> =
+            storage::dictionary_get(self.role_counts_dict_uref, PERMANENT_SUPER_ADMINS)
+                .unwrap_or_revert();
+        // Super admins never expired before expiring grants were introduced.
+        count.unwrap_or_else(|| self.role_count(Role::SuperAdmin))
+    }
+
+    /// Number of accounts in the admin list, including the disabled ones.
+    pub fn list_count(&self) -> u32 {
+        utils::get_key(ADMIN_LIST_COUNT).unwrap_or_default()
+    }
+
+    /// Returns up to `limit` accounts of the admin list starting with the `offset`-th one,
+    /// together with their role flags. Disabled admins have no roles.
+    pub fn list(&self, offset: u32, limit: u32) -> Vec<(Key, u8)> {
+        let end = offset
+            .saturating_add(limit.min(MAX_ADMINS_PAGE_SIZE))
+            .min(self.list_count());
+        (offset..end)
+            .map(|index| {
+                let account =
+                    utils::get_identity(self.list_dict_uref, &index.to_string()).unwrap_or_revert();
+                (account, self.roles(&account))
+            })
+            .collect()
+    }
+
+    /// Nominates `account` as a super admin, whose roles expire at `expires_at` if set.
+    pub fn add(&self, account: &Key, expires_at: Option<u64>) {
         if self.is_admin(account) {
             runtime::revert(ContractError::AdminExists);
-        } else {
-            storage::dictionary_put(self.dict_uref, &account.to_string(), ADMIN_ACTIVE);
+        }
+        if let Some(expires_at) = expires_at {
+            if expires_at <= utils::get_blocktime() {
+                runtime::revert(ContractError::InvalidExpiry);
+            }
+        }
+        self.nominate(account, Role::SuperAdmin, expires_at);
+    }
+
+    /// Grants `role` to `account` if it is an admin already, otherwise nominates it for `role`.
+    /// Nominating an account again refreshes the deadline and the expiry,
+    /// and adds to the nominated roles.
+    pub fn nominate(&self, account: &Key, role: Role, expires_at: Option<u64>) {
+        if self.is_admin(account) {
+            self.grant(account, role);
+            return;
+        }
+        let now = utils::get_blocktime();
+        let roles = match self.nomination(account) {
+            Some((deadline, roles, _)) if now <= deadline => roles,
+            _ => 0,
+        };
+        let nomination = (
+            now.saturating_add(NOMINATION_PERIOD),
+            roles | role as u8,
+            expires_at.unwrap_or(NO_EXPIRY),
+        );
+        storage::dictionary_put(
+            self.nominations_dict_uref,
+            &utils::identity_key(account),
+            nomination,
+        );
+    }
+
+    /// Grants the roles `account` has been nominated for.
+    pub fn accept(&self, account: &Key) {
+        let (deadline, roles, expires_at) = self
+            .nomination(account)
+            .filter(|(_, roles, _)| *roles != 0)
+            .unwrap_or_revert_with(ContractError::NominationNotFound);
+        if utils::get_blocktime() > deadline {
+            runtime::revert(ContractError::NominationExpired);
+        }
+        storage::dictionary_put(
+            self.nominations_dict_uref,
+            &utils::identity_key(account),
+            (0u64, 0u8, NO_EXPIRY),
+        );
+
+        // Roles of an expired grant are still stored and counted.
+        if self.is_expired(account) {
+            self.revoke_all(account);
+        }
+        storage::dictionary_put(
+            self.expiry_dict_uref,
+            &utils::identity_key(account),
+            expires_at,
+        );
+        for role in ALL_ROLES.iter() {
+            if roles & *role as u8 != 0 {
+                self.grant(account, *role);
+            }
+        }
+    }
+
+    /// Withdraws the pending nomination of `account`, so it can't be accepted anymore.
+    pub fn cancel_nomination(&self, account: &Key) {
+        self.nomination(account)
+            .filter(|(_, roles, _)| *roles != 0)
+            .unwrap_or_revert_with(ContractError::NominationNotFound);
+        storage::dictionary_put(
+            self.nominations_dict_uref,
+            &utils::identity_key(account),
+            (0u64, 0u8, NO_EXPIRY),
+        );
+    }
+
+    /// Returns the deadline, the role flags and the expiry of the nomination of `account`.
+    pub fn nomination(&self, account: &Key) -> Option<(u64, u8, u64)> {
+        storage::dictionary_get(self.nominations_dict_uref, &utils::identity_key(account))
+            .unwrap_or_revert()
+    }
 
-            // Increment the admin count.
-            let admins_count: u32 = utils::get_key(ADMINS_COUNT).unwrap_or_revert();
-            utils::set_key(ADMINS_COUNT, admins_count + 1);
+    /// Revokes all roles of `account`, including the ones of an expired grant.
+    pub fn disable(&self, account: &Key) {
+        if self.stored_roles(account) == 0 {
+            runtime::revert(ContractError::AdminDoesntExist);
         }
+        self.revoke_all(account);
     }
 
-    pub fn disable(&self, account: &AccountHash) {
-        // Make sure the last admin can't remove itself.
-        let admins_count: u32 = utils::get_key(ADMINS_COUNT).unwrap_or_revert();
-        if admins_count == 1 {
+    /// Makes `accounts` super admins without an expiry and revokes the roles of every other
+    /// listed admin. Admins added before the admin list was introduced are not listed until
+    /// `list_legacy_admin`, so their roles can't be revoked: the restore reverts while any
+    /// unlisted one has its roles.
+    pub fn restore(&self, accounts: &[Key]) {
+        if accounts.is_empty() {
             runtime::revert(ContractError::AdminCountToLow);
         }
+        for account in accounts {
+            if self.expires_at(account).is_some() {
+                self.revoke_all(account);
+            }
+            if self.stored_roles(account) & Role::SuperAdmin as u8 == 0 {
+                self.grant(account, Role::SuperAdmin);
+            }
+        }
+        for index in 0..self.list_count() {
+            let account =
+                utils::get_identity(self.list_dict_uref, &index.to_string()).unwrap_or_revert();
+            if !accounts.contains(&account) && self.stored_roles(&account) != 0 {
+                self.revoke_all(&account);
+            }
+        }
 
-        // Decrement admins count.
-        utils::set_key(ADMINS_COUNT, admins_count - 1);
+        // Every listed super admin is one of `accounts` now, any other one is an unlisted admin.
+        let restored = accounts.iter().collect::<BTreeSet<_>>().len() as u32;
+        if self.role_count(Role::SuperAdmin) > restored {
+            runtime::revert(ContractError::RecoveryNotAvailable);
+        }
+    }
 
-        if self.is_admin(account) {
-            storage::dictionary_put(self.dict_uref, &account.to_string(), ADMIN_DISABLED);
-        } else {
+    /// Stores the roles of `account`, an admin added by a contract version without roles,
+    /// and appends it to the admin list, so its roles can be revoked by a recovery.
+    /// The role counts already include it.
+    pub fn list_legacy_admin(&self, account: &Key) {
+        let roles: Option<u8> =
+            storage::dictionary_get(self.roles_dict_uref, &utils::identity_key(account))
+                .unwrap_or_revert();
+        if roles.is_some() || !self.is_legacy_admin(account) {
             runtime::revert(ContractError::AdminDoesntExist);
         }
+        self.set_roles(account, Role::SuperAdmin as u8);
+    }
+
+    pub fn grant(&self, account: &Key, role: Role) {
+        let roles = self.stored_roles(account);
+        if roles & role as u8 != 0 {
+            runtime::revert(ContractError::RoleAlreadyGranted);
+        }
+        if role == Role::SuperAdmin {
+            // Always store the count, so that it stops falling back to the super admin count.
+            let permanent = self.expires_at(account).is_none() as u32;
+            self.set_permanent_super_admins(self.permanent_super_admins() + permanent);
+        }
+        self.set_roles(account, roles | role as u8);
+        self.set_role_count(role, self.role_count(role) + 1);
     }
 
-    pub fn assert_caller_is_admin(&self) {
-        if !self.is_admin(&utils::get_caller()) {
+    /// Revokes `role` from `account`. Roles of an expired grant can be revoked as well.
+    pub fn revoke(&self, account: &Key, role: Role) {
+        let roles = self.stored_roles(account);
+        if roles & role as u8 == 0 {
+            runtime::revert(ContractError::RoleNotGranted);
+        }
+
+        // Make sure the last super admin without an expiry can't be removed.
+        if role == Role::SuperAdmin {
+            let mut permanent_super_admins = self.permanent_super_admins();
+            if self.expires_at(account).is_none() {
+                if permanent_super_admins <= 1 {
+                    runtime::revert(ContractError::AdminCountToLow);
+                }
+                permanent_super_admins -= 1;
+            }
+            self.set_permanent_super_admins(permanent_super_admins);
+        }
+
+        self.set_roles(account, roles & !(role as u8));
+        self.set_role_count(role, self.role_count(role) - 1);
+    }
+
+    pub fn assert_caller_has_role(&self, role: Role) {
+        if !self.has_role(&utils::get_caller(), role) {
             runtime::revert(ContractError::PermissionDenied);
         }
     }
+
+    /// Returns the stored role flags of `account`, ignoring the expiry. Admins added
+    /// before roles were introduced have no roles stored and are super admins.
+    fn stored_roles(&self, account: &Key) -> u8 {
+        let roles: Option<u8> =
+            storage::dictionary_get(self.roles_dict_uref, &utils::identity_key(account))
+                .unwrap_or_revert();
+        match roles {
+            Some(roles) => roles,
+            None if self.is_legacy_admin(account) => Role::SuperAdmin as u8,
+            None => 0,
+        }
+    }
+
+    fn is_expired(&self, account: &Key) -> bool {
+        match self.expires_at(account) {
+            Some(expires_at) => utils::get_blocktime() >= expires_at,
+            None => false,
+        }
+    }
+
+    fn revoke_all(&self, account: &Key) {
+        let roles = self.stored_roles(account);
+        for role in ALL_ROLES.iter() {
+            if roles & *role as u8 != 0 {
+                self.revoke(account, *role);
+            }
+        }
+        storage::dictionary_put(
+            self.expiry_dict_uref,
+            &utils::identity_key(account),
+            NO_EXPIRY,
+        );
+    }
+
+    fn is_legacy_admin(&self, account: &Key) -> bool {
+        let result: Option<bool> =
+            storage::dictionary_get(self.dict_uref, &utils::identity_key(account))
+                .unwrap_or_revert();
+        result == Some(ADMIN_ACTIVE)
+    }
+
+    /// Appends `account` to the admin list, unless it is there already.
+    fn append_to_list(&self, account: &Key) {
+        let position: Option<u32> =
+            storage::dictionary_get(self.list_index_dict_uref, &utils::identity_key(account))
+                .unwrap_or_revert();
+        if position.is_some() {
+            return;
+        }
+        let count = self.list_count();
+        storage::dictionary_put(self.list_dict_uref, &count.to_string(), *account);
+        storage::dictionary_put(
+            self.list_index_dict_uref,
+            &utils::identity_key(account),
+            count,
+        );
+        utils::set_key(ADMIN_LIST_COUNT, count + 1);
+    }
+
+    fn set_roles(&self, account: &Key, roles: u8) {
+        if roles != 0 {
+            self.append_to_list(account);
+        }
+        storage::dictionary_put(self.roles_dict_uref, &utils::identity_key(account), roles);
+        let status = if roles == 0 {
+            ADMIN_DISABLED
+        } else {
+            ADMIN_ACTIVE
+        };
+        storage::dictionary_put(self.dict_uref, &utils::identity_key(account), status);
+    }
+
+    fn set_role_count(&self, role: Role, count: u32) {
+        storage::dictionary_put(self.role_counts_dict_uref, &role_key(role), count);
+    }
+
+    fn set_permanent_super_admins(&self, count: u32) {
+        storage::dictionary_put(self.role_counts_dict_uref, PERMANENT_SUPER_ADMINS, count);
+    }
+}
+
+fn role_key(role: Role) -> String {
+    (role as u8).to_string()
 }
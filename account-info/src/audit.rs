@@ -0,0 +1,153 @@
+use contract::{contract_api::storage, unwrap_or_revert::UnwrapOrRevert};
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, Key, URef,
+};
+
+use crate::{recovery, utils};
+
+pub const AUDIT_LOG_DICT: &str = "account-info-audit-log";
+pub const AUDIT_LOG_COUNT: &str = "audit_log_count";
+pub const MAX_AUDIT_LOG_PAGE_SIZE: u32 = 50;
+
+// Actions run through proposals, see `AdminAction::audit_kind`.
+pub const ADD_ADMIN: u8 = 1;
+pub const DISABLE_ADMIN: u8 = 2;
+pub const GRANT_ROLE: u8 = 3;
+pub const REVOKE_ROLE: u8 = 4;
+pub const DELETE_URL_FOR_ACCOUNT: u8 = 5;
+pub const SET_APPROVAL_THRESHOLD: u8 = 7;
+pub const SET_TIMELOCK_DELAY: u8 = 8;
+pub const DELETE_URL_AND_SLASH_DEPOSIT: u8 = 21;
+pub const SET_REGISTRATION_FEE: u8 = 23;
+
+pub const SET_URL_FOR_ACCOUNT: u8 = 9;
+pub const SET_HTTPS_ONLY: u8 = 10;
+pub const SET_FEE_AFTER_DELETE: u8 = 11;
+pub const ALLOW_SCHEME: u8 = 12;
+pub const DISALLOW_SCHEME: u8 = 13;
+pub const BLOCK_DOMAIN: u8 = 14;
+pub const UNBLOCK_DOMAIN: u8 = 15;
+pub const SET_MAX_ACCOUNTS_PER_DOMAIN: u8 = 16;
+pub const PAUSE: u8 = 17;
+pub const UNPAUSE: u8 = 18;
+pub const RECOVER_ADMINS: u8 = 19;
+pub const WITHDRAW: u8 = 20;
+pub const SET_DEPOSIT_AMOUNT: u8 = 22;
+pub const SET_MIN_UPDATE_INTERVAL: u8 = 24;
+pub const CANCEL_NOMINATION: u8 = 25;
+
+/// Admin action recorded in the audit log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub actor: Key,
+    pub kind: u8,
+    /// Account or contract package the action was taken on, if any.
+    pub target: Option<Key>,
+    /// Arguments of the action, serialized in the order the entry point declares them.
+    pub args: Vec<u8>,
+    /// Block time of the action, in milliseconds.
+    pub timestamp: u64,
+}
+
+/// Tuple with the same serialization as the entry: the actor, the kind and the target,
+/// then the arguments and the timestamp.
+type AuditEntryTuple = ((Key, u8, Option<Key>), Vec<u8>, u64);
+
+impl CLTyped for AuditEntry {
+    fn cl_type() -> CLType {
+        AuditEntryTuple::cl_type()
+    }
+}
+
+impl ToBytes for AuditEntry {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::with_capacity(self.serialized_length());
+        result.append(&mut self.actor.to_bytes()?);
+        result.append(&mut self.kind.to_bytes()?);
+        result.append(&mut self.target.to_bytes()?);
+        result.append(&mut self.args.to_bytes()?);
+        result.append(&mut self.timestamp.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.actor.serialized_length()
+            + self.kind.serialized_length()
+            + self.target.serialized_length()
+            + self.args.serialized_length()
+            + self.timestamp.serialized_length()
+    }
+}
+
+impl FromBytes for AuditEntry {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (actor, remainder) = utils::identity_from_bytes(bytes)?;
+        let (kind, remainder) = u8::from_bytes(remainder)?;
+        let (target, remainder) = match remainder.split_first() {
+            Some((0, remainder)) => (None, remainder),
+            Some((1, remainder)) => {
+                let (target, remainder) = utils::identity_from_bytes(remainder)?;
+                (Some(target), remainder)
+            }
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        let (args, remainder) = Vec::<u8>::from_bytes(remainder)?;
+        let (timestamp, remainder) = u64::from_bytes(remainder)?;
+        let entry = AuditEntry {
+            actor,
+            kind,
+            target,
+            args,
+            timestamp,
+        };
+        Ok((entry, remainder))
+    }
+}
+
+/// Append-only log of the admin actions, stored under their sequence number.
+pub struct AuditLog {
+    dict_uref: URef,
+}
+
+impl AuditLog {
+    pub fn new() -> AuditLog {
+        AuditLog {
+            dict_uref: utils::get_dictionary(AUDIT_LOG_DICT),
+        }
+    }
+
+    /// Records the `kind` action taken by `actor` on `target` with the serialized `args`.
+    pub fn append(&self, actor: &Key, kind: u8, target: Option<Key>, args: Vec<u8>) {
+        let entry = AuditEntry {
+            actor: *actor,
+            kind,
+            target,
+            args,
+            timestamp: utils::get_blocktime(),
+        };
+        let count = self.count();
+        storage::dictionary_put(self.dict_uref, &count.to_string(), entry);
+        utils::set_key(AUDIT_LOG_COUNT, count + 1);
+        recovery::record_admin_activity();
+    }
+
+    /// Number of recorded actions.
+    pub fn count(&self) -> u32 {
+        utils::get_key(AUDIT_LOG_COUNT).unwrap_or_default()
+    }
+
+    /// Returns up to `limit` actions starting with the `offset`-th one, oldest first.
+    pub fn get(&self, offset: u32, limit: u32) -> Vec<AuditEntry> {
+        let end = offset
+            .saturating_add(limit.min(MAX_AUDIT_LOG_PAGE_SIZE))
+            .min(self.count());
+        (offset..end)
+            .map(|index| {
+                let entry: Option<AuditEntry> =
+                    storage::dictionary_get(self.dict_uref, &index.to_string()).unwrap_or_revert();
+                entry.unwrap_or_revert()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,65 @@
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::URef;
+
+use crate::{url, utils, ContractError};
+
+pub const BLOCKLIST_DICT: &str = "account-info-blocked-domains";
+pub const DOMAIN_UNBLOCKED: u8 = 0;
+pub const DOMAIN_BLOCKED: u8 = 1;
+pub const DOMAIN_AND_SUBDOMAINS_BLOCKED: u8 = 2;
+
+/// Admin-managed list of hosts that can't be used in the stored URLs.
+/// Domains are stored under the hash of their canonical form, as they can be longer
+/// than a dictionary item key.
+pub struct Blocklist {
+    dict_uref: URef,
+}
+
+impl Blocklist {
+    pub fn new() -> Blocklist {
+        Blocklist {
+            dict_uref: utils::get_dictionary(BLOCKLIST_DICT),
+        }
+    }
+
+    /// Blocks `domain`, and all of its subdomains if `include_subdomains` is set.
+    pub fn block(&self, domain: &str, include_subdomains: bool) {
+        let domain = url::canonical_domain(domain).unwrap_or_revert();
+        let value = if include_subdomains {
+            DOMAIN_AND_SUBDOMAINS_BLOCKED
+        } else {
+            DOMAIN_BLOCKED
+        };
+        storage::dictionary_put(self.dict_uref, &domain_key(&domain), value);
+    }
+
+    pub fn unblock(&self, domain: &str) {
+        let domain = url::canonical_domain(domain).unwrap_or_revert();
+        if self.get(&domain) == DOMAIN_UNBLOCKED {
+            runtime::revert(ContractError::NotFound);
+        }
+        storage::dictionary_put(self.dict_uref, &domain_key(&domain), DOMAIN_UNBLOCKED);
+    }
+
+    /// Whether the canonical `host` is blocked itself or as a subdomain of a blocked domain.
+    pub fn is_blocked(&self, host: &str) -> bool {
+        if self.get(host) != DOMAIN_UNBLOCKED {
+            return true;
+        }
+        host.match_indices('.')
+            .any(|(index, _)| self.get(&host[index + 1..]) == DOMAIN_AND_SUBDOMAINS_BLOCKED)
+    }
+
+    fn get(&self, domain: &str) -> u8 {
+        let value: Option<u8> =
+            storage::dictionary_get(self.dict_uref, &domain_key(domain)).unwrap_or_revert();
+        value.unwrap_or(DOMAIN_UNBLOCKED)
+    }
+}
+
+fn domain_key(domain: &str) -> String {
+    utils::dictionary_key(&[domain.as_bytes()])
+}
@@ -0,0 +1,115 @@
+use contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{Key, URef, U512};
+
+use crate::{fees, utils, ContractError};
+
+pub const DEPOSITS_DICT: &str = "account-info-deposits";
+/// Named key of the contract purse holding the locked deposits.
+pub const DEPOSIT_PURSE: &str = "account-info-deposit-purse";
+pub const DEPOSIT_AMOUNT: &str = "deposit_amount";
+/// Time a deposit stays locked before it can be refunded, in milliseconds.
+pub const DEPOSIT_HOLDING_PERIOD: u64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Refundable deposits locked by the accounts when they register a URL,
+/// stored as the amount in motes and the block time at which they were locked.
+pub struct Deposits {
+    dict_uref: URef,
+    purse: URef,
+}
+
+impl Deposits {
+    pub fn new() -> Deposits {
+        Deposits {
+            dict_uref: utils::get_dictionary(DEPOSITS_DICT),
+            purse: utils::get_purse(DEPOSIT_PURSE),
+        }
+    }
+
+    /// Returns the amount and the lock time of the deposit of `account`, if there is one.
+    pub fn get(&self, account: &Key) -> Option<(U512, u64)> {
+        let deposit: Option<(U512, u64)> =
+            storage::dictionary_get(self.dict_uref, &utils::identity_key(account))
+                .unwrap_or_revert();
+        deposit.filter(|(amount, _)| !amount.is_zero())
+    }
+
+    /// Transfers the deposit amount from `purse`, unless `account` has a deposit locked already.
+    /// The purse is only needed if there is a deposit to lock.
+    pub fn lock(&self, account: &Key, purse: Option<URef>) {
+        let amount = deposit_amount();
+        if amount.is_zero() || self.get(account).is_some() {
+            return;
+        }
+        let purse = purse.unwrap_or_revert_with(ContractError::PurseRequired);
+        system::transfer_from_purse_to_purse(purse, self.purse, amount, None)
+            .unwrap_or_revert_with(ContractError::FeePaymentFailed);
+        storage::dictionary_put(
+            self.dict_uref,
+            &utils::identity_key(account),
+            (amount, utils::get_blocktime()),
+        );
+    }
+
+    /// Whether `account` has a deposit locked for at least `DEPOSIT_HOLDING_PERIOD`.
+    pub fn is_refundable(&self, account: &Key) -> bool {
+        match self.get(account) {
+            Some((_, locked_at)) => {
+                utils::get_blocktime() >= locked_at.saturating_add(DEPOSIT_HOLDING_PERIOD)
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the deposit of `account` to `purse`, or to the account itself if no purse is given.
+    pub fn refund(&self, account: &Key, purse: Option<URef>) {
+        let (amount, _) = self
+            .get(account)
+            .unwrap_or_revert_with(ContractError::NotFound);
+        if !self.is_refundable(account) {
+            runtime::revert(ContractError::DepositLocked);
+        }
+        self.clear(account);
+        let result = match (purse, account) {
+            (Some(purse), _) => {
+                system::transfer_from_purse_to_purse(self.purse, purse, amount, None)
+            }
+            (None, Key::Account(account_hash)) => {
+                system::transfer_from_purse_to_account(self.purse, *account_hash, amount, None)
+                    .map(|_| ())
+            }
+            (None, _) => runtime::revert(ContractError::PurseRequired),
+        };
+        result.unwrap_or_revert_with(ContractError::RefundFailed);
+    }
+
+    /// Moves the deposit of `account`, if any, to the treasury.
+    pub fn slash(&self, account: &Key) {
+        if let Some((amount, _)) = self.get(account) {
+            self.clear(account);
+            system::transfer_from_purse_to_purse(self.purse, fees::treasury(), amount, None)
+                .unwrap_or_revert_with(ContractError::SlashFailed);
+        }
+    }
+
+    /// Sum of the locked deposits in motes.
+    pub fn balance(&self) -> U512 {
+        system::get_purse_balance(self.purse).unwrap_or_revert()
+    }
+
+    fn clear(&self, account: &Key) {
+        storage::dictionary_put(
+            self.dict_uref,
+            &utils::identity_key(account),
+            (U512::zero(), 0u64),
+        );
+    }
+}
+
+/// Deposit locked with the first `set_url` call, in motes. Contracts installed
+/// before deposits were introduced don't require one until it is set.
+pub fn deposit_amount() -> U512 {
+    utils::get_key(DEPOSIT_AMOUNT).unwrap_or_default()
+}
@@ -0,0 +1,126 @@
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{Key, URef};
+
+use crate::{utils, ContractError};
+
+pub const DOMAIN_ACCOUNTS_DICT: &str = "account-info-domain-accounts";
+pub const DOMAIN_ACCOUNTS_COUNT_DICT: &str = "account-info-domain-accounts-count";
+pub const DOMAIN_ACCOUNT_INDEX_DICT: &str = "account-info-domain-account-index";
+pub const ACCOUNT_DOMAIN_DICT: &str = "account-info-account-domain";
+pub const MAX_ACCOUNTS_PER_DOMAIN: &str = "max_accounts_per_domain";
+pub const MAX_DOMAIN_ACCOUNTS_PAGE_SIZE: u32 = 50;
+
+/// Reverse index from the canonical host of the stored URLs to the accounts using it.
+/// Every domain has a list of accounts, stored under the hash of the domain and the position
+/// in the list, and the position of every account in the list, stored under the hash
+/// of the domain and the account, so accounts can be removed by swapping them with the last one.
+pub struct DomainIndex {
+    accounts_dict_uref: URef,
+    count_dict_uref: URef,
+    index_dict_uref: URef,
+    account_domain_dict_uref: URef,
+}
+
+impl DomainIndex {
+    pub fn new() -> DomainIndex {
+        DomainIndex {
+            accounts_dict_uref: utils::get_dictionary(DOMAIN_ACCOUNTS_DICT),
+            count_dict_uref: utils::get_dictionary(DOMAIN_ACCOUNTS_COUNT_DICT),
+            index_dict_uref: utils::get_dictionary(DOMAIN_ACCOUNT_INDEX_DICT),
+            account_domain_dict_uref: utils::get_dictionary(ACCOUNT_DOMAIN_DICT),
+        }
+    }
+
+    /// Moves `address` to the list of `domain`, or only removes it from its current list
+    /// if `domain` is `None`.
+    pub fn update(&self, address: &Key, domain: Option<&str>) {
+        let current: Option<String> =
+            storage::dictionary_get(self.account_domain_dict_uref, &utils::identity_key(address))
+                .unwrap_or_revert();
+        let current = current.filter(|current| !current.is_empty());
+        if current.as_deref() == domain {
+            return;
+        }
+
+        if let Some(current) = current {
+            self.remove(&current, address);
+        }
+        if let Some(domain) = domain {
+            self.add(domain, address);
+        }
+        storage::dictionary_put(
+            self.account_domain_dict_uref,
+            &utils::identity_key(address),
+            domain.unwrap_or_default(),
+        );
+    }
+
+    /// Number of accounts using `domain`.
+    pub fn count(&self, domain: &str) -> u32 {
+        let count: Option<u32> =
+            storage::dictionary_get(self.count_dict_uref, &count_key(domain)).unwrap_or_revert();
+        count.unwrap_or_default()
+    }
+
+    /// Returns up to `limit` accounts using `domain`, starting with the `offset`-th one.
+    pub fn get(&self, domain: &str, offset: u32, limit: u32) -> Vec<Key> {
+        let end = offset
+            .saturating_add(limit.min(MAX_DOMAIN_ACCOUNTS_PAGE_SIZE))
+            .min(self.count(domain));
+        (offset..end)
+            .map(|index| self.account_at(domain, index))
+            .collect()
+    }
+
+    fn add(&self, domain: &str, address: &Key) {
+        let count = self.count(domain);
+        let max_accounts: u32 = utils::get_key(MAX_ACCOUNTS_PER_DOMAIN).unwrap_or_default();
+        if max_accounts > 0 && count >= max_accounts {
+            runtime::revert(ContractError::DomainAccountLimitReached);
+        }
+
+        storage::dictionary_put(self.accounts_dict_uref, &entry_key(domain, count), *address);
+        // Positions are stored incremented by one, so zero means the account is not in the list.
+        storage::dictionary_put(self.index_dict_uref, &index_key(domain, address), count + 1);
+        storage::dictionary_put(self.count_dict_uref, &count_key(domain), count + 1);
+    }
+
+    fn remove(&self, domain: &str, address: &Key) {
+        let position: Option<u32> =
+            storage::dictionary_get(self.index_dict_uref, &index_key(domain, address))
+                .unwrap_or_revert();
+        let index = match position {
+            Some(position) if position > 0 => position - 1,
+            _ => return,
+        };
+
+        // Move the last account of the list to the position of the removed one.
+        let last_index = self.count(domain) - 1;
+        if index != last_index {
+            let last = self.account_at(domain, last_index);
+            storage::dictionary_put(self.accounts_dict_uref, &entry_key(domain, index), last);
+            storage::dictionary_put(self.index_dict_uref, &index_key(domain, &last), index + 1);
+        }
+        storage::dictionary_put(self.index_dict_uref, &index_key(domain, address), 0u32);
+        storage::dictionary_put(self.count_dict_uref, &count_key(domain), last_index);
+    }
+
+    fn account_at(&self, domain: &str, index: u32) -> Key {
+        utils::get_identity(self.accounts_dict_uref, &entry_key(domain, index)).unwrap_or_revert()
+    }
+}
+
+fn count_key(domain: &str) -> String {
+    utils::dictionary_key(&[domain.as_bytes()])
+}
+
+fn entry_key(domain: &str, index: u32) -> String {
+    utils::dictionary_key(&[domain.as_bytes(), &index.to_le_bytes()])
+}
+
+fn index_key(domain: &str, address: &Key) -> String {
+    utils::dictionary_key(&[domain.as_bytes(), &utils::identity_bytes(address)])
+}
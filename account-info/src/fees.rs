@@ -0,0 +1,67 @@
+use contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{Key, URef, U512};
+
+use crate::{utils, ContractError};
+
+/// Named key of the contract main purse collecting the registration fees.
+pub const TREASURY_PURSE: &str = "account-info-treasury";
+pub const REGISTRATION_FEE: &str = "registration_fee";
+/// Named key of the registration fee in whole CSPR, stored before it was stored in motes.
+pub const LEGACY_CSPR_TO_BURN: &str = "cspr_to_burn";
+pub const MOTES_PER_CSPR: u64 = 1_000_000_000;
+
+/// Registration fee in motes.
+pub fn registration_fee() -> U512 {
+    utils::get_key(REGISTRATION_FEE).unwrap_or_revert()
+}
+
+/// Converts the fee in whole CSPR of earlier versions into the fee in motes.
+/// Does nothing once the fee is stored in motes.
+pub fn migrate_registration_fee() {
+    if runtime::has_key(REGISTRATION_FEE) {
+        return;
+    }
+    let cspr_to_burn: u32 = utils::get_key(LEGACY_CSPR_TO_BURN).unwrap_or_default();
+    utils::set_key(REGISTRATION_FEE, U512::from(cspr_to_burn) * MOTES_PER_CSPR);
+    runtime::remove_key(LEGACY_CSPR_TO_BURN);
+}
+
+pub fn treasury() -> URef {
+    utils::get_purse(TREASURY_PURSE)
+}
+
+/// Balance of the treasury in motes.
+pub fn treasury_balance() -> U512 {
+    system::get_purse_balance(treasury()).unwrap_or_revert()
+}
+
+/// Transfers the registration fee from `purse` to the treasury.
+/// The purse is only needed if there is a fee.
+pub fn charge(purse: Option<URef>) {
+    let fee = registration_fee();
+    if fee.is_zero() {
+        return;
+    }
+    let purse = purse.unwrap_or_revert_with(ContractError::PurseRequired);
+    system::transfer_from_purse_to_purse(purse, treasury(), fee, None)
+        .unwrap_or_revert_with(ContractError::FeePaymentFailed);
+}
+
+/// Transfers `amount` motes from the treasury to the `target` account or purse.
+pub fn withdraw(amount: U512, target: &Key) {
+    if amount > treasury_balance() {
+        runtime::revert(ContractError::InsufficientTreasuryBalance);
+    }
+    let result = match target {
+        Key::Account(account_hash) => {
+            system::transfer_from_purse_to_account(treasury(), *account_hash, amount, None)
+                .map(|_| ())
+        }
+        Key::URef(purse) => system::transfer_from_purse_to_purse(treasury(), *purse, amount, None),
+        _ => runtime::revert(ContractError::InvalidWithdrawalTarget),
+    };
+    result.unwrap_or_revert_with(ContractError::WithdrawalFailed);
+}
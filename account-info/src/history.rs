@@ -0,0 +1,63 @@
+use contract::{contract_api::storage, unwrap_or_revert::UnwrapOrRevert};
+use types::{Key, URef};
+
+use crate::{record::AccountInfoRecord, utils};
+
+pub const HISTORY_DICT: &str = "account-info-url-history";
+pub const HISTORY_COUNT_DICT: &str = "account-info-url-history-count";
+pub const MAX_HISTORY_PAGE_SIZE: u32 = 50;
+
+/// Append-only list of the URL changes of every account.
+/// Entries are stored under the hash of the identity and the sequence number of the change.
+pub struct History {
+    dict_uref: URef,
+    count_dict_uref: URef,
+}
+
+impl History {
+    pub fn new() -> History {
+        History {
+            dict_uref: utils::get_dictionary(HISTORY_DICT),
+            count_dict_uref: utils::get_dictionary(HISTORY_COUNT_DICT),
+        }
+    }
+
+    pub fn append(&self, address: &Key, record: &AccountInfoRecord) {
+        let count = self.count(address);
+        storage::dictionary_put(self.dict_uref, &entry_key(address, count), record.clone());
+        storage::dictionary_put(
+            self.count_dict_uref,
+            &utils::identity_key(address),
+            count + 1,
+        );
+    }
+
+    /// Number of changes recorded for `address`.
+    pub fn count(&self, address: &Key) -> u32 {
+        let count: Option<u32> =
+            storage::dictionary_get(self.count_dict_uref, &utils::identity_key(address))
+                .unwrap_or_revert();
+        count.unwrap_or_default()
+    }
+
+    /// Returns up to `limit` changes of `address` starting with the `offset`-th one, oldest first.
+    pub fn get(&self, address: &Key, offset: u32, limit: u32) -> Vec<AccountInfoRecord> {
+        let end = offset
+            .saturating_add(limit.min(MAX_HISTORY_PAGE_SIZE))
+            .min(self.count(address));
+        (offset..end)
+            .map(|index| {
+                storage::dictionary_get::<AccountInfoRecord>(
+                    self.dict_uref,
+                    &entry_key(address, index),
+                )
+                .unwrap_or_revert()
+                .unwrap_or_revert()
+            })
+            .collect()
+    }
+}
+
+fn entry_key(address: &Key, index: u32) -> String {
+    utils::dictionary_key(&[&utils::identity_bytes(address), &index.to_le_bytes()])
+}
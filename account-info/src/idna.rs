@@ -0,0 +1,500 @@
+use core::cmp::Ordering;
+
+const HANGUL_SYLLABLE_FIRST: u32 = 0xAC00;
+const HANGUL_SYLLABLE_LAST: u32 = 0xD7A3;
+const HANGUL_LEADING_FIRST: u32 = 0x1100;
+const HANGUL_LEADING_LAST: u32 = 0x1112;
+const HANGUL_VOWEL_FIRST: u32 = 0x1161;
+const HANGUL_VOWEL_LAST: u32 = 0x1175;
+const HANGUL_TRAILING_FIRST: u32 = 0x11A8;
+const HANGUL_TRAILING_LAST: u32 = 0x11C2;
+const HANGUL_TRAILING_COUNT: u32 = 28;
+
+/// Whether a lower-cased host label is left unchanged by the UTS #46 mapping and the NFC
+/// normalization browsers apply before converting a host to punycode. Any other label resolves
+/// to a different host than the one its punycode form names, e.g. `ｅｘａｍｐｌｅ` resolves to
+/// `example`, and would get around the domain blocklist and the accounts-per-domain index.
+///
+/// The tables below are derived from the UTS #46 mapping table and the Unicode character
+/// database, for the characters `char::is_alphanumeric` accepts and their lower-case forms.
+pub fn is_normalized(label: &str) -> bool {
+    let mut starter = None;
+    // Combining class of the previous character, zero for a starter.
+    let mut last_class = 0;
+    for c in label.chars() {
+        if find_range(MAPPED, c, |&range| range).is_some() {
+            return false;
+        }
+        let class = find_range(COMBINING_CLASSES, c, |&(first, last, _)| (first, last))
+            .map_or(0, |&(_, _, class)| class);
+        // NFC reorders marks by their combining class...
+        if class != 0 && class < last_class {
+            return false;
+        }
+        // ...and composes a starter with a following character that isn't blocked by a mark
+        // of the same or a higher combining class in between.
+        let blocked = last_class != 0 && (class == 0 || last_class >= class);
+        if let Some(starter) = starter {
+            if !blocked && composes(starter, c) {
+                return false;
+            }
+        }
+        if class == 0 {
+            starter = Some(c);
+        }
+        last_class = class;
+    }
+    true
+}
+
+fn composes(first: char, second: char) -> bool {
+    let (a, b) = (first as u32, second as u32);
+    let leading_vowel = (HANGUL_LEADING_FIRST..=HANGUL_LEADING_LAST).contains(&a)
+        && (HANGUL_VOWEL_FIRST..=HANGUL_VOWEL_LAST).contains(&b);
+    let syllable_trailing = (HANGUL_SYLLABLE_FIRST..=HANGUL_SYLLABLE_LAST).contains(&a)
+        && (a - HANGUL_SYLLABLE_FIRST) % HANGUL_TRAILING_COUNT == 0
+        && (HANGUL_TRAILING_FIRST..=HANGUL_TRAILING_LAST).contains(&b);
+    leading_vowel || syllable_trailing || COMPOSITIONS.binary_search(&(first, second)).is_ok()
+}
+
+fn find_range<T>(table: &[T], c: char, range: impl Fn(&T) -> (char, char)) -> Option<&T> {
+    table
+        .binary_search_by(|entry| {
+            let (first, last) = range(entry);
+            if last < c {
+                Ordering::Less
+            } else if first > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .ok()
+        .map(|index| &table[index])
+}
+
+/// Characters that UTS #46 maps or disallows, or that NFC replaces.
+const MAPPED: &[(char, char)] = &[
+    ('\u{AA}', '\u{AA}'),
+    ('\u{B2}', '\u{B3}'),
+    ('\u{B5}', '\u{B5}'),
+    ('\u{B9}', '\u{BA}'),
+    ('\u{BC}', '\u{BE}'),
+    ('\u{133}', '\u{133}'),
+    ('\u{140}', '\u{140}'),
+    ('\u{149}', '\u{149}'),
+    ('\u{17F}', '\u{17F}'),
+    ('\u{1C6}', '\u{1C6}'),
+    ('\u{1C9}', '\u{1C9}'),
+    ('\u{1CC}', '\u{1CC}'),
+    ('\u{1F3}', '\u{1F3}'),
+    ('\u{2B0}', '\u{2B8}'),
+    ('\u{2E0}', '\u{2E4}'),
+    ('\u{345}', '\u{345}'),
+    ('\u{374}', '\u{374}'),
+    ('\u{37A}', '\u{37A}'),
+    ('\u{3D0}', '\u{3D6}'),
+    ('\u{3F0}', '\u{3F2}'),
+    ('\u{3F5}', '\u{3F5}'),
+    ('\u{587}', '\u{587}'),
+    ('\u{675}', '\u{678}'),
+    ('\u{958}', '\u{95F}'),
+    ('\u{9DC}', '\u{9DD}'),
+    ('\u{9DF}', '\u{9DF}'),
+    ('\u{A33}', '\u{A33}'),
+    ('\u{A36}', '\u{A36}'),
+    ('\u{A59}', '\u{A5B}'),
+    ('\u{A5E}', '\u{A5E}'),
+    ('\u{B5C}', '\u{B5D}'),
+    ('\u{E33}', '\u{E33}'),
+    ('\u{EB3}', '\u{EB3}'),
+    ('\u{EDC}', '\u{EDD}'),
+    ('\u{F43}', '\u{F43}'),
+    ('\u{F4D}', '\u{F4D}'),
+    ('\u{F52}', '\u{F52}'),
+    ('\u{F57}', '\u{F57}'),
+    ('\u{F5C}', '\u{F5C}'),
+    ('\u{F69}', '\u{F69}'),
+    ('\u{F73}', '\u{F73}'),
+    ('\u{F75}', '\u{F79}'),
+    ('\u{F81}', '\u{F81}'),
+    ('\u{F93}', '\u{F93}'),
+    ('\u{F9D}', '\u{F9D}'),
+    ('\u{FA2}', '\u{FA2}'),
+    ('\u{FA7}', '\u{FA7}'),
+    ('\u{FAC}', '\u{FAC}'),
+    ('\u{FB9}', '\u{FB9}'),
+    ('\u{10FC}', '\u{10FC}'),
+    ('\u{115F}', '\u{1160}'),
+    ('\u{13F8}', '\u{13FD}'),
+    ('\u{1C80}', '\u{1C88}'),
+    ('\u{1D2C}', '\u{1D2E}'),
+    ('\u{1D30}', '\u{1D3A}'),
+    ('\u{1D3C}', '\u{1D4D}'),
+    ('\u{1D4F}', '\u{1D6A}'),
+    ('\u{1D78}', '\u{1D78}'),
+    ('\u{1D9B}', '\u{1DBF}'),
+    ('\u{1E9A}', '\u{1E9B}'),
+    ('\u{1F71}', '\u{1F71}'),
+    ('\u{1F73}', '\u{1F73}'),
+    ('\u{1F75}', '\u{1F75}'),
+    ('\u{1F77}', '\u{1F77}'),
+    ('\u{1F79}', '\u{1F79}'),
+    ('\u{1F7B}', '\u{1F7B}'),
+    ('\u{1F7D}', '\u{1F7D}'),
+    ('\u{1F80}', '\u{1F87}'),
+    ('\u{1F90}', '\u{1F97}'),
+    ('\u{1FA0}', '\u{1FA7}'),
+    ('\u{1FB2}', '\u{1FB4}'),
+    ('\u{1FB7}', '\u{1FB7}'),
+    ('\u{1FBE}', '\u{1FBE}'),
+    ('\u{1FC2}', '\u{1FC4}'),
+    ('\u{1FC7}', '\u{1FC7}'),
+    ('\u{1FD3}', '\u{1FD3}'),
+    ('\u{1FE3}', '\u{1FE3}'),
+    ('\u{1FF2}', '\u{1FF4}'),
+    ('\u{1FF7}', '\u{1FF7}'),
+    ('\u{2070}', '\u{2071}'),
+    ('\u{2074}', '\u{2079}'),
+    ('\u{207F}', '\u{2089}'),
+    ('\u{2090}', '\u{209C}'),
+    ('\u{2102}', '\u{2102}'),
+    ('\u{2107}', '\u{2107}'),
+    ('\u{210A}', '\u{2113}'),
+    ('\u{2115}', '\u{2115}'),
+    ('\u{2119}', '\u{211D}'),
+    ('\u{2124}', '\u{2124}'),
+    ('\u{2128}', '\u{2128}'),
+    ('\u{212C}', '\u{212D}'),
+    ('\u{212F}', '\u{2131}'),
+    ('\u{2133}', '\u{2139}'),
+    ('\u{213C}', '\u{213F}'),
+    ('\u{2145}', '\u{2149}'),
+    ('\u{2150}', '\u{215F}'),
+    ('\u{2170}', '\u{217F}'),
+    ('\u{2189}', '\u{2189}'),
+    ('\u{2460}', '\u{249B}'),
+    ('\u{24D0}', '\u{24EA}'),
+    ('\u{2C7C}', '\u{2C7D}'),
+    ('\u{2D6F}', '\u{2D6F}'),
+    ('\u{3038}', '\u{303A}'),
+    ('\u{309F}', '\u{309F}'),
+    ('\u{30FF}', '\u{30FF}'),
+    ('\u{3131}', '\u{318E}'),
+    ('\u{3192}', '\u{3195}'),
+    ('\u{3220}', '\u{3229}'),
+    ('\u{3251}', '\u{325F}'),
+    ('\u{3280}', '\u{3289}'),
+    ('\u{32B1}', '\u{32BF}'),
+    ('\u{A69C}', '\u{A69D}'),
+    ('\u{A770}', '\u{A770}'),
+    ('\u{A7F1}', '\u{A7F4}'),
+    ('\u{A7F8}', '\u{A7F9}'),
+    ('\u{AB5C}', '\u{AB5F}'),
+    ('\u{AB69}', '\u{AB69}'),
+    ('\u{AB70}', '\u{ABBF}'),
+    ('\u{F900}', '\u{FA0D}'),
+    ('\u{FA10}', '\u{FA10}'),
+    ('\u{FA12}', '\u{FA12}'),
+    ('\u{FA15}', '\u{FA1E}'),
+    ('\u{FA20}', '\u{FA20}'),
+    ('\u{FA22}', '\u{FA22}'),
+    ('\u{FA25}', '\u{FA26}'),
+    ('\u{FA2A}', '\u{FA6D}'),
+    ('\u{FA70}', '\u{FAD9}'),
+    ('\u{FB00}', '\u{FB06}'),
+    ('\u{FB13}', '\u{FB17}'),
+    ('\u{FB1D}', '\u{FB1D}'),
+    ('\u{FB1F}', '\u{FB28}'),
+    ('\u{FB2A}', '\u{FB36}'),
+    ('\u{FB38}', '\u{FB3C}'),
+    ('\u{FB3E}', '\u{FB3E}'),
+    ('\u{FB40}', '\u{FB41}'),
+    ('\u{FB43}', '\u{FB44}'),
+    ('\u{FB46}', '\u{FBB1}'),
+    ('\u{FBD3}', '\u{FD3D}'),
+    ('\u{FD50}', '\u{FD8F}'),
+    ('\u{FD92}', '\u{FDC7}'),
+    ('\u{FDF0}', '\u{FDFB}'),
+    ('\u{FE70}', '\u{FE72}'),
+    ('\u{FE74}', '\u{FE74}'),
+    ('\u{FE76}', '\u{FEFC}'),
+    ('\u{FF10}', '\u{FF19}'),
+    ('\u{FF41}', '\u{FF5A}'),
+    ('\u{FF66}', '\u{FFBE}'),
+    ('\u{FFC2}', '\u{FFC7}'),
+    ('\u{FFCA}', '\u{FFCF}'),
+    ('\u{FFD2}', '\u{FFD7}'),
+    ('\u{FFDA}', '\u{FFDC}'),
+    ('\u{10781}', '\u{10785}'),
+    ('\u{10787}', '\u{107B0}'),
+    ('\u{107B2}', '\u{107BA}'),
+    ('\u{1CCF0}', '\u{1CCF9}'),
+    ('\u{1D400}', '\u{1D454}'),
+    ('\u{1D456}', '\u{1D49C}'),
+    ('\u{1D49E}', '\u{1D49F}'),
+    ('\u{1D4A2}', '\u{1D4A2}'),
+    ('\u{1D4A5}', '\u{1D4A6}'),
+    ('\u{1D4A9}', '\u{1D4AC}'),
+    ('\u{1D4AE}', '\u{1D4B9}'),
+    ('\u{1D4BB}', '\u{1D4BB}'),
+    ('\u{1D4BD}', '\u{1D4C3}'),
+    ('\u{1D4C5}', '\u{1D505}'),
+    ('\u{1D507}', '\u{1D50A}'),
+    ('\u{1D50D}', '\u{1D514}'),
+    ('\u{1D516}', '\u{1D51C}'),
+    ('\u{1D51E}', '\u{1D539}'),
+    ('\u{1D53B}', '\u{1D53E}'),
+    ('\u{1D540}', '\u{1D544}'),
+    ('\u{1D546}', '\u{1D546}'),
+    ('\u{1D54A}', '\u{1D550}'),
+    ('\u{1D552}', '\u{1D6A5}'),
+    ('\u{1D6A8}', '\u{1D6C0}'),
+    ('\u{1D6C2}', '\u{1D6DA}'),
+    ('\u{1D6DC}', '\u{1D6FA}'),
+    ('\u{1D6FC}', '\u{1D714}'),
+    ('\u{1D716}', '\u{1D734}'),
+    ('\u{1D736}', '\u{1D74E}'),
+    ('\u{1D750}', '\u{1D76E}'),
+    ('\u{1D770}', '\u{1D788}'),
+    ('\u{1D78A}', '\u{1D7A8}'),
+    ('\u{1D7AA}', '\u{1D7C2}'),
+    ('\u{1D7C4}', '\u{1D7CB}'),
+    ('\u{1D7CE}', '\u{1D7FF}'),
+    ('\u{1E030}', '\u{1E06D}'),
+    ('\u{1EE00}', '\u{1EE03}'),
+    ('\u{1EE05}', '\u{1EE1F}'),
+    ('\u{1EE21}', '\u{1EE22}'),
+    ('\u{1EE24}', '\u{1EE24}'),
+    ('\u{1EE27}', '\u{1EE27}'),
+    ('\u{1EE29}', '\u{1EE32}'),
+    ('\u{1EE34}', '\u{1EE37}'),
+    ('\u{1EE39}', '\u{1EE39}'),
+    ('\u{1EE3B}', '\u{1EE3B}'),
+    ('\u{1EE42}', '\u{1EE42}'),
+    ('\u{1EE47}', '\u{1EE47}'),
+    ('\u{1EE49}', '\u{1EE49}'),
+    ('\u{1EE4B}', '\u{1EE4B}'),
+    ('\u{1EE4D}', '\u{1EE4F}'),
+    ('\u{1EE51}', '\u{1EE52}'),
+    ('\u{1EE54}', '\u{1EE54}'),
+    ('\u{1EE57}', '\u{1EE57}'),
+    ('\u{1EE59}', '\u{1EE59}'),
+    ('\u{1EE5B}', '\u{1EE5B}'),
+    ('\u{1EE5D}', '\u{1EE5D}'),
+    ('\u{1EE5F}', '\u{1EE5F}'),
+    ('\u{1EE61}', '\u{1EE62}'),
+    ('\u{1EE64}', '\u{1EE64}'),
+    ('\u{1EE67}', '\u{1EE6A}'),
+    ('\u{1EE6C}', '\u{1EE72}'),
+    ('\u{1EE74}', '\u{1EE77}'),
+    ('\u{1EE79}', '\u{1EE7C}'),
+    ('\u{1EE7E}', '\u{1EE7E}'),
+    ('\u{1EE80}', '\u{1EE89}'),
+    ('\u{1EE8B}', '\u{1EE9B}'),
+    ('\u{1EEA1}', '\u{1EEA3}'),
+    ('\u{1EEA5}', '\u{1EEA9}'),
+    ('\u{1EEAB}', '\u{1EEBB}'),
+    ('\u{1F100}', '\u{1F10A}'),
+    ('\u{1F130}', '\u{1F149}'),
+    ('\u{1FBF0}', '\u{1FBF9}'),
+    ('\u{2F800}', '\u{2FA1D}'),
+];
+
+/// Canonical combining classes of the marks that can appear in a label.
+const COMBINING_CLASSES: &[(char, char, u8)] = &[
+    ('\u{307}', '\u{307}', 230),
+    ('\u{363}', '\u{36F}', 230),
+    ('\u{5B0}', '\u{5B0}', 10),
+    ('\u{5B1}', '\u{5B1}', 11),
+    ('\u{5B2}', '\u{5B2}', 12),
+    ('\u{5B3}', '\u{5B3}', 13),
+    ('\u{5B4}', '\u{5B4}', 14),
+    ('\u{5B5}', '\u{5B5}', 15),
+    ('\u{5B6}', '\u{5B6}', 16),
+    ('\u{5B7}', '\u{5B7}', 17),
+    ('\u{5B8}', '\u{5B8}', 18),
+    ('\u{5B9}', '\u{5BA}', 19),
+    ('\u{5BB}', '\u{5BB}', 20),
+    ('\u{5BC}', '\u{5BC}', 21),
+    ('\u{5BD}', '\u{5BD}', 22),
+    ('\u{5BF}', '\u{5BF}', 23),
+    ('\u{5C1}', '\u{5C1}', 24),
+    ('\u{5C2}', '\u{5C2}', 25),
+    ('\u{5C4}', '\u{5C4}', 230),
+    ('\u{5C5}', '\u{5C5}', 220),
+    ('\u{5C7}', '\u{5C7}', 18),
+    ('\u{610}', '\u{617}', 230),
+    ('\u{618}', '\u{618}', 30),
+    ('\u{619}', '\u{619}', 31),
+    ('\u{61A}', '\u{61A}', 32),
+    ('\u{64B}', '\u{64B}', 27),
+    ('\u{64C}', '\u{64C}', 28),
+    ('\u{64D}', '\u{64D}', 29),
+    ('\u{64E}', '\u{64E}', 30),
+    ('\u{64F}', '\u{64F}', 31),
+    ('\u{650}', '\u{650}', 32),
+    ('\u{651}', '\u{651}', 33),
+    ('\u{652}', '\u{652}', 34),
+    ('\u{653}', '\u{654}', 230),
+    ('\u{655}', '\u{656}', 220),
+    ('\u{657}', '\u{657}', 230),
+    ('\u{659}', '\u{65B}', 230),
+    ('\u{65C}', '\u{65C}', 220),
+    ('\u{65D}', '\u{65E}', 230),
+    ('\u{65F}', '\u{65F}', 220),
+    ('\u{670}', '\u{670}', 35),
+    ('\u{6D6}', '\u{6DC}', 230),
+    ('\u{6E1}', '\u{6E2}', 230),
+    ('\u{6E3}', '\u{6E3}', 220),
+    ('\u{6E4}', '\u{6E4}', 230),
+    ('\u{6E7}', '\u{6E8}', 230),
+    ('\u{6ED}', '\u{6ED}', 220),
+    ('\u{711}', '\u{711}', 36),
+    ('\u{730}', '\u{730}', 230),
+    ('\u{731}', '\u{731}', 220),
+    ('\u{732}', '\u{733}', 230),
+    ('\u{734}', '\u{734}', 220),
+    ('\u{735}', '\u{736}', 230),
+    ('\u{737}', '\u{739}', 220),
+    ('\u{73A}', '\u{73A}', 230),
+    ('\u{73B}', '\u{73C}', 220),
+    ('\u{73D}', '\u{73D}', 230),
+    ('\u{73E}', '\u{73E}', 220),
+    ('\u{73F}', '\u{73F}', 230),
+    ('\u{816}', '\u{817}', 230),
+    ('\u{81B}', '\u{823}', 230),
+    ('\u{825}', '\u{827}', 230),
+    ('\u{829}', '\u{82C}', 230),
+    ('\u{8D4}', '\u{8DF}', 230),
+    ('\u{8E3}', '\u{8E3}', 220),
+    ('\u{8E4}', '\u{8E5}', 230),
+    ('\u{8E6}', '\u{8E6}', 220),
+    ('\u{8E7}', '\u{8E8}', 230),
+    ('\u{8E9}', '\u{8E9}', 220),
+    ('\u{8F0}', '\u{8F0}', 27),
+    ('\u{8F1}', '\u{8F1}', 28),
+    ('\u{8F2}', '\u{8F2}', 29),
+    ('\u{8F3}', '\u{8F5}', 230),
+    ('\u{8F6}', '\u{8F6}', 220),
+    ('\u{8F7}', '\u{8F8}', 230),
+    ('\u{8F9}', '\u{8FA}', 220),
+    ('\u{8FB}', '\u{8FF}', 230),
+    ('\u{C55}', '\u{C55}', 84),
+    ('\u{C56}', '\u{C56}', 91),
+    ('\u{E38}', '\u{E39}', 103),
+    ('\u{E3A}', '\u{E3A}', 9),
+    ('\u{EB8}', '\u{EB9}', 118),
+    ('\u{F71}', '\u{F71}', 129),
+    ('\u{F72}', '\u{F72}', 130),
+    ('\u{F74}', '\u{F74}', 132),
+    ('\u{F7A}', '\u{F7D}', 130),
+    ('\u{F80}', '\u{F80}', 130),
+    ('\u{F82}', '\u{F83}', 230),
+    ('\u{108D}', '\u{108D}', 220),
+    ('\u{18A9}', '\u{18A9}', 228),
+    ('\u{1A17}', '\u{1A17}', 230),
+    ('\u{1A18}', '\u{1A18}', 220),
+    ('\u{1ABF}', '\u{1AC0}', 220),
+    ('\u{1ACC}', '\u{1ACE}', 230),
+    ('\u{1DD3}', '\u{1DF4}', 230),
+    ('\u{2DE0}', '\u{2DFF}', 230),
+    ('\u{A674}', '\u{A67B}', 230),
+    ('\u{A69E}', '\u{A69F}', 230),
+    ('\u{AAB0}', '\u{AAB0}', 230),
+    ('\u{AAB2}', '\u{AAB3}', 230),
+    ('\u{AAB4}', '\u{AAB4}', 220),
+    ('\u{AAB7}', '\u{AAB8}', 230),
+    ('\u{AABE}', '\u{AABE}', 230),
+    ('\u{FB1E}', '\u{FB1E}', 26),
+    ('\u{10376}', '\u{1037A}', 230),
+    ('\u{10A0D}', '\u{10A0D}', 220),
+    ('\u{10A0F}', '\u{10A0F}', 230),
+    ('\u{10D24}', '\u{10D27}', 230),
+    ('\u{10EAB}', '\u{10EAC}', 230),
+    ('\u{11100}', '\u{11102}', 230),
+    ('\u{16FF0}', '\u{16FF1}', 6),
+    ('\u{1BC9E}', '\u{1BC9E}', 1),
+    ('\u{1E000}', '\u{1E006}', 230),
+    ('\u{1E008}', '\u{1E018}', 230),
+    ('\u{1E01B}', '\u{1E021}', 230),
+    ('\u{1E023}', '\u{1E024}', 230),
+    ('\u{1E026}', '\u{1E02A}', 230),
+    ('\u{1E947}', '\u{1E947}', 230),
+];
+
+/// Pairs of characters NFC composes into a single character, besides the Hangul syllables.
+const COMPOSITIONS: &[(char, char)] = &[
+    ('\u{61}', '\u{307}'),
+    ('\u{62}', '\u{307}'),
+    ('\u{63}', '\u{307}'),
+    ('\u{64}', '\u{307}'),
+    ('\u{65}', '\u{307}'),
+    ('\u{66}', '\u{307}'),
+    ('\u{67}', '\u{307}'),
+    ('\u{68}', '\u{307}'),
+    ('\u{6D}', '\u{307}'),
+    ('\u{6E}', '\u{307}'),
+    ('\u{6F}', '\u{307}'),
+    ('\u{70}', '\u{307}'),
+    ('\u{72}', '\u{307}'),
+    ('\u{73}', '\u{307}'),
+    ('\u{74}', '\u{307}'),
+    ('\u{77}', '\u{307}'),
+    ('\u{78}', '\u{307}'),
+    ('\u{79}', '\u{307}'),
+    ('\u{7A}', '\u{307}'),
+    ('\u{15B}', '\u{307}'),
+    ('\u{161}', '\u{307}'),
+    ('\u{627}', '\u{653}'),
+    ('\u{627}', '\u{654}'),
+    ('\u{627}', '\u{655}'),
+    ('\u{648}', '\u{654}'),
+    ('\u{64A}', '\u{654}'),
+    ('\u{6C1}', '\u{654}'),
+    ('\u{6D2}', '\u{654}'),
+    ('\u{6D5}', '\u{654}'),
+    ('\u{9C7}', '\u{9BE}'),
+    ('\u{9C7}', '\u{9D7}'),
+    ('\u{B47}', '\u{B3E}'),
+    ('\u{B47}', '\u{B56}'),
+    ('\u{B47}', '\u{B57}'),
+    ('\u{B92}', '\u{BD7}'),
+    ('\u{BC6}', '\u{BBE}'),
+    ('\u{BC6}', '\u{BD7}'),
+    ('\u{BC7}', '\u{BBE}'),
+    ('\u{C46}', '\u{C56}'),
+    ('\u{CBF}', '\u{CD5}'),
+    ('\u{CC6}', '\u{CC2}'),
+    ('\u{CC6}', '\u{CD5}'),
+    ('\u{CC6}', '\u{CD6}'),
+    ('\u{CCA}', '\u{CD5}'),
+    ('\u{D46}', '\u{D3E}'),
+    ('\u{D46}', '\u{D57}'),
+    ('\u{D47}', '\u{D3E}'),
+    ('\u{DD9}', '\u{DCF}'),
+    ('\u{DD9}', '\u{DDF}'),
+    ('\u{1025}', '\u{102E}'),
+    ('\u{1B05}', '\u{1B35}'),
+    ('\u{1B07}', '\u{1B35}'),
+    ('\u{1B09}', '\u{1B35}'),
+    ('\u{1B0B}', '\u{1B35}'),
+    ('\u{1B0D}', '\u{1B35}'),
+    ('\u{1B11}', '\u{1B35}'),
+    ('\u{1B3A}', '\u{1B35}'),
+    ('\u{1B3C}', '\u{1B35}'),
+    ('\u{1B3E}', '\u{1B35}'),
+    ('\u{1B3F}', '\u{1B35}'),
+    ('\u{1B42}', '\u{1B35}'),
+    ('\u{1E63}', '\u{307}'),
+    ('\u{11131}', '\u{11127}'),
+    ('\u{11132}', '\u{11127}'),
+    ('\u{11347}', '\u{1133E}'),
+    ('\u{11347}', '\u{11357}'),
+    ('\u{114B9}', '\u{114B0}'),
+    ('\u{114B9}', '\u{114BA}'),
+    ('\u{114B9}', '\u{114BD}'),
+    ('\u{115B8}', '\u{115AF}'),
+    ('\u{115B9}', '\u{115AF}'),
+    ('\u{11935}', '\u{11930}'),
+];
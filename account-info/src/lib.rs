@@ -1,23 +1,52 @@
 extern crate alloc;
 
+use alloc::collections::BTreeSet;
+
 use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::AccountHash, contracts::NamedKeys, CLTyped};
+use types::{bytesrepr::ToBytes, contracts::NamedKeys, CLTyped, Key, URef, U512};
 
 use types::{
     contracts::ContractPackageHash, ApiError, CLType, CLValue, EntryPoint, EntryPointAccess,
-    EntryPointType, EntryPoints, Parameter,
+    EntryPointType, EntryPoints, Group, Parameter, RuntimeArgs,
 };
 
 mod admins;
+mod audit;
+mod blocklist;
+mod deposits;
+mod domains;
+mod fees;
+mod history;
+mod idna;
+mod proposals;
+mod punycode;
+mod record;
+mod recovery;
+mod schemes;
+mod url;
 mod urls;
 mod utils;
 
-use admins::Admins;
+use admins::{Admins, Role};
+use audit::{AuditEntry, AuditLog};
+use blocklist::Blocklist;
+use deposits::Deposits;
+use domains::DomainIndex;
+use history::History;
+use proposals::{AdminAction, Proposal, Proposals};
+use record::{AccountInfoRecord, Origin};
+use schemes::{SchemeRule, Schemes};
 use urls::Urls;
 
+/// Named key of the flag that stops all state changes while set.
+const PAUSED: &str = "paused";
+
+/// User group of the entry points only the installer can call, see `migrate`.
+const INSTALLER_GROUP: &str = "installer";
+
 #[derive(Debug)]
 pub enum ContractError {
     NotFound = 1,
@@ -29,6 +58,47 @@ pub enum ContractError {
     AdminDoesntExist = 7,
     CallerIsNotAccount = 8,
     ReadingCallerError = 9,
+    UrlTooLong = 10,
+    UrlInvalidCharacter = 11,
+    UrlInvalidScheme = 12,
+    UrlMissingHost = 13,
+    UrlHostTooLong = 14,
+    UrlInvalidHost = 15,
+    UrlInvalidPort = 16,
+    UrlHasUserInfo = 17,
+    UrlHasQuery = 18,
+    UrlHasFragment = 19,
+    UrlHasWellKnownPath = 20,
+    InsecureUrlScheme = 21,
+    InvalidContentHash = 22,
+    InvalidSchemeRule = 23,
+    UrlSchemeNotAllowed = 24,
+    DomainBlocked = 25,
+    DomainAccountLimitReached = 26,
+    InvalidRole = 27,
+    RoleAlreadyGranted = 28,
+    RoleNotGranted = 29,
+    InvalidAction = 30,
+    AlreadyApproved = 31,
+    NotEnoughApprovals = 32,
+    ProposalNotPending = 33,
+    InvalidThreshold = 34,
+    TimelockNotExpired = 35,
+    NominationNotFound = 36,
+    NominationExpired = 37,
+    InvalidExpiry = 38,
+    ContractPaused = 39,
+    InvalidIdentity = 40,
+    RecoveryNotAvailable = 41,
+    PurseRequired = 42,
+    FeePaymentFailed = 43,
+    InsufficientTreasuryBalance = 44,
+    InvalidWithdrawalTarget = 45,
+    WithdrawalFailed = 46,
+    DepositLocked = 47,
+    RefundFailed = 48,
+    TooManyUpdates = 49,
+    SlashFailed = 50,
 }
 
 impl From<ContractError> for ApiError {
@@ -42,21 +112,61 @@ pub fn get_entry_points() -> EntryPoints {
     let mut entry_points = EntryPoints::new();
     entry_points.add_entry_point(EntryPoint::new(
         "set_url",
-        vec![Parameter::new("url", CLType::String)],
+        vec![
+            Parameter::new("url", CLType::String),
+            Parameter::new("content_hash", Option::<String>::cl_type()),
+            Parameter::new("purse", Option::<URef>::cl_type()),
+        ],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
         "get_url",
-        vec![Parameter::new("account", AccountHash::cl_type())],
+        vec![Parameter::new("account", Key::cl_type())],
         CLType::String,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_record",
+        vec![Parameter::new("account", Key::cl_type())],
+        AccountInfoRecord::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_url_history",
+        vec![
+            Parameter::new("account", Key::cl_type()),
+            Parameter::new("offset", CLType::U32),
+            Parameter::new("limit", CLType::U32),
+        ],
+        Vec::<AccountInfoRecord>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_accounts_for_domain",
+        vec![
+            Parameter::new("domain", CLType::String),
+            Parameter::new("offset", CLType::U32),
+            Parameter::new("limit", CLType::U32),
+        ],
+        Vec::<Key>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "delete_url",
-        vec![],
+        vec![Parameter::new("purse", Option::<URef>::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "refund_deposit",
+        vec![Parameter::new("purse", Option::<URef>::cl_type())],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
@@ -64,8 +174,9 @@ pub fn get_entry_points() -> EntryPoints {
     entry_points.add_entry_point(EntryPoint::new(
         "set_url_for_account",
         vec![
-            Parameter::new("account", AccountHash::cl_type()),
+            Parameter::new("account", Key::cl_type()),
             Parameter::new("url", CLType::String),
+            Parameter::new("content_hash", Option::<String>::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -73,35 +184,261 @@ pub fn get_entry_points() -> EntryPoints {
     ));
     entry_points.add_entry_point(EntryPoint::new(
         "delete_url_for_account",
-        vec![Parameter::new("account", AccountHash::cl_type())],
+        vec![
+            Parameter::new("account", Key::cl_type()),
+            Parameter::new("slash_deposit", Option::<bool>::cl_type()),
+        ],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
         "add_admin",
-        vec![Parameter::new("account", AccountHash::cl_type())],
+        vec![
+            Parameter::new("account", Key::cl_type()),
+            Parameter::new("expires_at", Option::<u64>::cl_type()),
+        ],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
         "disable_admin",
-        vec![Parameter::new("account", AccountHash::cl_type())],
+        vec![Parameter::new("account", Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "accept_admin",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_nomination",
+        vec![Parameter::new("account", Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "list_admins",
+        vec![
+            Parameter::new("offset", CLType::U32),
+            Parameter::new("limit", CLType::U32),
+        ],
+        Vec::<(Key, u8)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_audit_log",
+        vec![
+            Parameter::new("offset", CLType::U32),
+            Parameter::new("limit", CLType::U32),
+        ],
+        Vec::<AuditEntry>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "grant_role",
+        vec![
+            Parameter::new("account", Key::cl_type()),
+            Parameter::new("role", CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "revoke_role",
+        vec![
+            Parameter::new("account", Key::cl_type()),
+            Parameter::new("role", CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_registration_fee",
+        vec![Parameter::new("registration_fee", CLType::U512)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_approval_threshold",
+        vec![Parameter::new("approval_threshold", CLType::U32)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_timelock_delay",
+        vec![Parameter::new("timelock_delay", CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "propose",
+        vec![
+            Parameter::new("action", CLType::U8),
+            Parameter::new("args", Vec::<u8>::cl_type()),
+        ],
+        CLType::U32,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "approve",
+        vec![Parameter::new("proposal_id", CLType::U32)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "execute",
+        vec![Parameter::new("proposal_id", CLType::U32)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel",
+        vec![Parameter::new("proposal_id", CLType::U32)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_proposal",
+        vec![Parameter::new("proposal_id", CLType::U32)],
+        Proposal::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_https_only",
+        vec![Parameter::new("https_only", CLType::Bool)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "allow_scheme",
+        vec![
+            Parameter::new("scheme", CLType::String),
+            Parameter::new("rule", CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "disallow_scheme",
+        vec![Parameter::new("scheme", CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "block_domain",
+        vec![
+            Parameter::new("domain", CLType::String),
+            Parameter::new("include_subdomains", CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "unblock_domain",
+        vec![Parameter::new("domain", CLType::String)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_min_update_interval",
+        vec![Parameter::new("min_update_interval", CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_max_accounts_per_domain",
+        vec![Parameter::new("max_accounts_per_domain", CLType::U32)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_fee_after_delete",
+        vec![Parameter::new("fee_after_delete", CLType::Bool)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_deposit_amount",
+        vec![Parameter::new("deposit_amount", CLType::U512)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "pause",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "unpause",
+        vec![],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
-        "burn_one_cspr",
+        "get_treasury_balance",
         vec![],
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_deposits_balance",
+        vec![],
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "withdraw",
+        vec![
+            Parameter::new("amount", CLType::U512),
+            Parameter::new("target", Key::cl_type()),
+        ],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
-        "set_cspr_to_burn",
-        vec![Parameter::new("cspr_to_burn", CLType::U32)],
+        "migrate",
+        vec![Parameter::new(admins::LEGACY_ADMINS, Vec::<Key>::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Groups(vec![Group::new(INSTALLER_GROUP)]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "recover_admins",
+        vec![Parameter::new("admins", Vec::<Key>::cl_type())],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
@@ -135,10 +472,25 @@ pub fn install_or_upgrade_contract(name: String) {
                 // Add deployer as the first admin.
                 let admin = utils::self_addr();
                 let admins_dict = storage::new_dictionary(admins::ADMINS_DICT).unwrap_or_revert();
-                storage::dictionary_put(admins_dict, &admin.to_string(), admins::ADMIN_ACTIVE);
+                storage::dictionary_put(
+                    admins_dict,
+                    &utils::identity_key(&admin),
+                    admins::ADMIN_ACTIVE,
+                );
                 named_keys.insert(admins::ADMINS_DICT.to_string(), admins_dict.into());
+                let super_admin = Role::SuperAdmin as u8;
+                let roles_dict = utils::get_or_create_dictionary(admins::ROLES_DICT);
+                storage::dictionary_put(roles_dict, &utils::identity_key(&admin), super_admin);
+                let role_counts_dict = utils::get_or_create_dictionary(admins::ROLE_COUNTS_DICT);
+                storage::dictionary_put(role_counts_dict, &super_admin.to_string(), 1u32);
+                storage::dictionary_put(role_counts_dict, admins::PERMANENT_SUPER_ADMINS, 1u32);
+                let admin_list_dict = utils::get_or_create_dictionary(admins::ADMIN_LIST_DICT);
+                storage::dictionary_put(admin_list_dict, "0", admin);
+                let admin_list_index_dict =
+                    utils::get_or_create_dictionary(admins::ADMIN_LIST_INDEX_DICT);
+                storage::dictionary_put(admin_list_index_dict, &utils::identity_key(&admin), 0u32);
                 named_keys.insert(
-                    admins::ADMINS_COUNT.to_string(),
+                    admins::ADMIN_LIST_COUNT.to_string(),
                     storage::new_uref(1u32).into(),
                 );
 
@@ -146,8 +498,73 @@ pub fn install_or_upgrade_contract(name: String) {
                 let urls_dict = storage::new_dictionary(urls::URLS_DICT).unwrap_or_revert();
                 named_keys.insert(urls::URLS_DICT.to_string(), urls_dict.into());
 
-                // Set initial gas_burn to 10 CSPR.
-                named_keys.insert("cspr_to_burn".to_string(), storage::new_uref(9u32).into());
+                // Only accept HTTPS URLs by default.
+                named_keys.insert(urls::HTTPS_ONLY.to_string(), storage::new_uref(true).into());
+
+                // Charge the fee again when registering after a deletion.
+                named_keys.insert(
+                    urls::FEE_AFTER_DELETE.to_string(),
+                    storage::new_uref(true).into(),
+                );
+
+                // Accounts can change their URL at any time.
+                named_keys.insert(
+                    urls::MIN_UPDATE_INTERVAL.to_string(),
+                    storage::new_uref(0u64).into(),
+                );
+
+                // No limit on the number of accounts per domain.
+                named_keys.insert(
+                    domains::MAX_ACCOUNTS_PER_DOMAIN.to_string(),
+                    storage::new_uref(0u32).into(),
+                );
+
+                // Sensitive admin actions run right away, without further approvals.
+                named_keys.insert(
+                    proposals::APPROVAL_THRESHOLD.to_string(),
+                    storage::new_uref(1u32).into(),
+                );
+
+                // The deployer has to choose the timelock delay of configuration changes.
+                let timelock_delay: u64 = runtime::get_named_arg(proposals::TIMELOCK_DELAY);
+                named_keys.insert(
+                    proposals::TIMELOCK_DELAY.to_string(),
+                    storage::new_uref(timelock_delay).into(),
+                );
+                named_keys.insert(
+                    proposals::PROPOSALS_COUNT.to_string(),
+                    storage::new_uref(0u32).into(),
+                );
+                named_keys.insert(
+                    audit::AUDIT_LOG_COUNT.to_string(),
+                    storage::new_uref(0u32).into(),
+                );
+
+                // Accept changes right away.
+                named_keys.insert(PAUSED.to_string(), storage::new_uref(false).into());
+
+                // Let the optional guardian recover the admins once they stop acting.
+                let guardian: Option<Key> = utils::get_optional_named_arg(recovery::GUARDIAN);
+                named_keys.insert(
+                    recovery::GUARDIAN.to_string(),
+                    storage::new_uref(guardian).into(),
+                );
+                named_keys.insert(
+                    recovery::LAST_ADMIN_ACTIVITY.to_string(),
+                    storage::new_uref(utils::get_blocktime()).into(),
+                );
+
+                // Lock a refundable deposit of 2 CSPR with the first registration.
+                named_keys.insert(
+                    deposits::DEPOSIT_AMOUNT.to_string(),
+                    storage::new_uref(U512::from(2) * fees::MOTES_PER_CSPR).into(),
+                );
+
+                // Set the initial registration fee to 9 CSPR.
+                named_keys.insert(
+                    fees::REGISTRATION_FEE.to_string(),
+                    storage::new_uref(U512::from(9) * fees::MOTES_PER_CSPR).into(),
+                );
 
                 // Store package hash.
                 named_keys.insert(
@@ -159,6 +576,73 @@ pub fn install_or_upgrade_contract(name: String) {
             }
         };
 
+    // Dictionaries added after the first release are created on upgrade as well.
+    let roles_dict = utils::get_or_create_dictionary(admins::ROLES_DICT);
+    named_keys.insert(admins::ROLES_DICT.to_string(), roles_dict.into());
+    let role_counts_dict = utils::get_or_create_dictionary(admins::ROLE_COUNTS_DICT);
+    named_keys.insert(
+        admins::ROLE_COUNTS_DICT.to_string(),
+        role_counts_dict.into(),
+    );
+    let admin_list_dict = utils::get_or_create_dictionary(admins::ADMIN_LIST_DICT);
+    named_keys.insert(admins::ADMIN_LIST_DICT.to_string(), admin_list_dict.into());
+    let nominations_dict = utils::get_or_create_dictionary(admins::NOMINATIONS_DICT);
+    named_keys.insert(
+        admins::NOMINATIONS_DICT.to_string(),
+        nominations_dict.into(),
+    );
+    let expiry_dict = utils::get_or_create_dictionary(admins::EXPIRY_DICT);
+    named_keys.insert(admins::EXPIRY_DICT.to_string(), expiry_dict.into());
+    let admin_list_index_dict = utils::get_or_create_dictionary(admins::ADMIN_LIST_INDEX_DICT);
+    named_keys.insert(
+        admins::ADMIN_LIST_INDEX_DICT.to_string(),
+        admin_list_index_dict.into(),
+    );
+    let proposals_dict = utils::get_or_create_dictionary(proposals::PROPOSALS_DICT);
+    named_keys.insert(proposals::PROPOSALS_DICT.to_string(), proposals_dict.into());
+    let url_status_dict = utils::get_or_create_dictionary(urls::URL_STATUS_DICT);
+    named_keys.insert(urls::URL_STATUS_DICT.to_string(), url_status_dict.into());
+    let records_dict = utils::get_or_create_dictionary(urls::RECORDS_DICT);
+    named_keys.insert(urls::RECORDS_DICT.to_string(), records_dict.into());
+    let blocklist_dict = utils::get_or_create_dictionary(blocklist::BLOCKLIST_DICT);
+    named_keys.insert(blocklist::BLOCKLIST_DICT.to_string(), blocklist_dict.into());
+    for dict_name in [
+        domains::DOMAIN_ACCOUNTS_DICT,
+        domains::DOMAIN_ACCOUNTS_COUNT_DICT,
+        domains::DOMAIN_ACCOUNT_INDEX_DICT,
+        domains::ACCOUNT_DOMAIN_DICT,
+    ]
+    .iter()
+    {
+        let dict = utils::get_or_create_dictionary(dict_name);
+        named_keys.insert(dict_name.to_string(), dict.into());
+    }
+    let history_dict = utils::get_or_create_dictionary(history::HISTORY_DICT);
+    named_keys.insert(history::HISTORY_DICT.to_string(), history_dict.into());
+    let history_count_dict = utils::get_or_create_dictionary(history::HISTORY_COUNT_DICT);
+    named_keys.insert(
+        history::HISTORY_COUNT_DICT.to_string(),
+        history_count_dict.into(),
+    );
+    let audit_log_dict = utils::get_or_create_dictionary(audit::AUDIT_LOG_DICT);
+    named_keys.insert(audit::AUDIT_LOG_DICT.to_string(), audit_log_dict.into());
+
+    let deposits_dict = utils::get_or_create_dictionary(deposits::DEPOSITS_DICT);
+    named_keys.insert(deposits::DEPOSITS_DICT.to_string(), deposits_dict.into());
+
+    // Allow HTTP(S) URLs when the scheme allowlist is created, either on install or on upgrade.
+    let schemes_dict = match runtime::get_key(schemes::SCHEMES_DICT) {
+        Some(dict_key) => *dict_key.as_uref().unwrap_or_revert(),
+        None => {
+            let schemes_dict = storage::new_dictionary(schemes::SCHEMES_DICT).unwrap_or_revert();
+            for scheme in schemes::DEFAULT_SCHEMES.iter() {
+                storage::dictionary_put(schemes_dict, scheme, SchemeRule::Host as u8);
+            }
+            schemes_dict
+        }
+    };
+    named_keys.insert(schemes::SCHEMES_DICT.to_string(), schemes_dict.into());
+
     let entry_points = get_entry_points();
     let (contract_hash, _) =
         storage::add_contract_version(contract_package_hash, entry_points, named_keys);
@@ -172,101 +656,532 @@ pub fn install_or_upgrade_contract(name: String) {
         &format!("{}-latest-version-contract-hash", name),
         storage::new_uref(contract_hash).into(),
     );
+
+    // Create the purses of the contract, convert the named keys of earlier versions and list
+    // the admins they added, given in the optional `legacy_admins` argument. The installer holds the only URef of the group while calling it.
+    let installer_uref = provision_installer_uref(contract_package_hash);
+    let legacy_admins: Vec<Key> =
+        utils::get_optional_named_arg(admins::LEGACY_ADMINS).unwrap_or_default();
+    let mut migrate_args = RuntimeArgs::new();
+    migrate_args
+        .insert(admins::LEGACY_ADMINS, legacy_admins)
+        .unwrap_or_revert();
+    runtime::call_contract::<()>(contract_hash, "migrate", migrate_args);
+    let mut installer_urefs = BTreeSet::new();
+    installer_urefs.insert(installer_uref);
+    storage::remove_contract_user_group_urefs(
+        contract_package_hash,
+        INSTALLER_GROUP,
+        installer_urefs,
+    )
+    .unwrap_or_revert();
+}
+
+/// Returns a new URef of the installer group, creating the group when installing
+/// the contract or upgrading a version without it.
+fn provision_installer_uref(contract_package_hash: ContractPackageHash) -> URef {
+    match storage::provision_contract_user_group_uref(contract_package_hash, INSTALLER_GROUP) {
+        Ok(uref) => uref,
+        Err(_) => storage::create_contract_user_group(
+            contract_package_hash,
+            INSTALLER_GROUP,
+            1,
+            BTreeSet::new(),
+        )
+        .unwrap_or_revert()
+        .pop()
+        .unwrap_or_revert(),
+    }
+}
+
+/// Reads the optional `content_hash` argument, the hex encoded blake2b-256 hash
+/// of the account info file content.
+fn get_content_hash_arg() -> Option<[u8; record::CONTENT_HASH_LENGTH]> {
+    utils::get_optional_named_arg::<String>("content_hash")
+        .map(|content_hash| urls::parse_content_hash(&content_hash))
+}
+
+/// Reverts with `ContractPaused` while the contract is paused, see `pause`.
+fn assert_not_paused() {
+    if utils::get_key::<bool>(PAUSED).unwrap_or_default() {
+        runtime::revert(ContractError::ContractPaused);
+    }
+}
+
+/// Records the admin action of the caller in the audit log.
+fn log_admin_action<T: ToBytes>(kind: u8, target: Option<Key>, args: T) {
+    let args = args.to_bytes().unwrap_or_revert();
+    AuditLog::new().append(&utils::get_caller(), kind, target, args);
 }
 
 // Entry points
 
 /// Stores the `url` parameter to the contract callers PublicKey.
-/// Needs to start with `http://` or `https://`.
+/// Needs to use one of the allowed schemes, `http://` and `https://` by default,
+/// and have no user info, query or fragment.
+/// Only `https://` is accepted while the HTTPS-only policy is enabled.
+/// The URL is stored in its canonical form, together with the optional `content_hash`
+/// of the account info file.
+/// The registration fee is transferred from the `purse` argument, when it is due.
 #[no_mangle]
 fn set_url() {
+    assert_not_paused();
     let caller = utils::get_caller();
     let url: String = runtime::get_named_arg("url");
+    let content_hash = get_content_hash_arg();
     let urls = Urls::new();
-    let first_time = urls.get(&caller).is_none();
-
-    // Burn CSPR if never done that before.
-    if first_time {
-        let package_hash: ContractPackageHash = utils::get_key("package_hash").unwrap_or_revert();
-        let cspr_to_burn: u32 = utils::get_key("cspr_to_burn").unwrap_or_revert();
-        for _ in 0..cspr_to_burn {
-            let _: Vec<u8> = runtime::call_versioned_contract(
-                package_hash,
-                None,
-                "burn_one_cspr",
-                Default::default(),
-            );
-        }
+    urls.assert_update_allowed(&caller);
+
+    // Charge the fee if never done that before, or after a deletion if required,
+    // and lock the deposit unless the caller has one locked already.
+    let purse: Option<URef> = utils::get_optional_named_arg("purse").flatten();
+    if urls.is_fee_required(&caller) {
+        fees::charge(purse);
     }
+    Deposits::new().lock(&caller, purse);
 
-    Urls::new().set(&caller, &url);
+    urls.set(&caller, &url, &caller, Origin::Owner, content_hash);
 }
 
 /// Getter function for stored URLs. Returns data stored under the `account` argument.
 #[no_mangle]
 fn get_url() {
-    let account = runtime::get_named_arg::<AccountHash>("account");
+    let account = utils::get_identity_arg("account");
     let url = Urls::new()
         .get(&account)
         .unwrap_or_revert_with(ContractError::NotFound);
     runtime::ret(CLValue::from_t(url).unwrap_or_revert());
 }
 
+/// Getter function for stored account info records. Returns the record stored under
+/// the `account` argument, including when and by whom the URL was last changed.
+#[no_mangle]
+fn get_record() {
+    let account = utils::get_identity_arg("account");
+    let record = Urls::new()
+        .get_record(&account)
+        .unwrap_or_revert_with(ContractError::NotFound);
+    runtime::ret(CLValue::from_t(record).unwrap_or_revert());
+}
+
+/// Getter function for the URL change history of the `account` argument.
+/// Returns up to `limit` changes, oldest first, starting with the `offset`-th one.
+#[no_mangle]
+fn get_url_history() {
+    let account = utils::get_identity_arg("account");
+    let offset: u32 = runtime::get_named_arg("offset");
+    let limit: u32 = runtime::get_named_arg("limit");
+    let history = History::new().get(&account, offset, limit);
+    runtime::ret(CLValue::from_t(history).unwrap_or_revert());
+}
+
+/// Getter function for the accounts whose stored URL has the `domain` host.
+/// Returns up to `limit` accounts starting with the `offset`-th one.
+#[no_mangle]
+fn get_accounts_for_domain() {
+    let domain: String = runtime::get_named_arg("domain");
+    let offset: u32 = runtime::get_named_arg("offset");
+    let limit: u32 = runtime::get_named_arg("limit");
+    let domain = url::canonical_domain(&domain).unwrap_or_revert();
+    let accounts = DomainIndex::new().get(&domain, offset, limit);
+    runtime::ret(CLValue::from_t(accounts).unwrap_or_revert());
+}
+
 /// Function so the caller can remove their stored URL from the contract.
+/// Fails with `NotFound` if the caller has no URL stored.
+/// Refunds the deposit to the optional `purse` argument, or to the calling account,
+/// if it has been locked for the holding period. Otherwise it stays locked until then.
 #[no_mangle]
 fn delete_url() {
+    assert_not_paused();
+    let caller = utils::get_caller();
+    Urls::new().delete(&caller, &caller, Origin::Owner);
+    let deposits = Deposits::new();
+    if deposits.is_refundable(&caller) {
+        let purse: Option<URef> = utils::get_optional_named_arg("purse").flatten();
+        deposits.refund(&caller, purse);
+    }
+}
+
+/// Function so the caller can get back their deposit once it has been locked for the holding
+/// period and they have no URL stored, to the optional `purse` argument or the calling account.
+#[no_mangle]
+fn refund_deposit() {
+    assert_not_paused();
     let caller = utils::get_caller();
-    Urls::new().delete(&caller);
+    if Urls::new().get(&caller).is_some() {
+        runtime::revert(ContractError::DepositLocked);
+    }
+    let purse: Option<URef> = utils::get_optional_named_arg("purse").flatten();
+    Deposits::new().refund(&caller, purse);
 }
 
 /// Administrator function that can create new or overwrite already existing urls stored under `PublicKey`es.
 /// Can still only store URLs.
 #[no_mangle]
 fn set_url_for_account() {
-    Admins::new().assert_caller_is_admin();
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::Moderator);
     let url: String = runtime::get_named_arg("url");
-    let account = runtime::get_named_arg("account");
-    Urls::new().set(&account, &url);
+    let account = utils::get_identity_arg("account");
+    let content_hash = get_content_hash_arg();
+    let url = Urls::new().set(
+        &account,
+        &url,
+        &utils::get_caller(),
+        Origin::Admin,
+        content_hash,
+    );
+    log_admin_action(
+        audit::SET_URL_FOR_ACCOUNT,
+        Some(account),
+        (account, url, content_hash),
+    );
 }
 
-/// Administrator function to remove stored data from the contract.
+/// Administrator function to remove stored data from the contract,
+/// moving the deposit of the account to the treasury if `slash_deposit` is set.
+/// Stored as a proposal instead if it needs more approvals.
 #[no_mangle]
 fn delete_url_for_account() {
-    Admins::new().assert_caller_is_admin();
-    let account = runtime::get_named_arg("account");
-    Urls::new().delete(&account);
+    assert_not_paused();
+    let account = utils::get_identity_arg("account");
+    let slash_deposit = utils::get_optional_named_arg::<bool>("slash_deposit").unwrap_or_default();
+    Proposals::new().submit(AdminAction::DeleteUrlForAccount(account, slash_deposit));
 }
 
-/// Administrator function to add another administrators.
+/// Administrator function to nominate another administrator,
+/// who becomes one after calling `accept_admin`, until `expires_at` if given.
+/// Stored as a proposal instead if it needs more approvals or the timelock delay is set.
 #[no_mangle]
 fn add_admin() {
-    let admins = Admins::new();
-    admins.assert_caller_is_admin();
-    let account = runtime::get_named_arg("account");
-    admins.add(&account);
+    assert_not_paused();
+    let account = utils::get_identity_arg("account");
+    let expires_at = utils::get_optional_named_arg::<u64>("expires_at");
+    Proposals::new().submit(AdminAction::AddAdmin(account, expires_at));
 }
 
-/// Administrator function to add another administrators.
+/// Administrator function to revoke all roles of an administrator.
+/// Stored as a proposal instead if it needs more approvals or the timelock delay is set.
 #[no_mangle]
 fn disable_admin() {
+    assert_not_paused();
+    let account = utils::get_identity_arg("account");
+    Proposals::new().submit(AdminAction::DisableAdmin(account));
+}
+
+/// Function so the caller can accept its nomination and get the nominated roles.
+#[no_mangle]
+fn accept_admin() {
+    assert_not_paused();
+    Admins::new().accept(&utils::get_caller());
+}
+
+/// Administrator function to withdraw the pending nomination of `account`, e.g. one made
+/// for a mistyped or compromised account. Runs right away, so the account can't accept it meanwhile.
+#[no_mangle]
+fn cancel_nomination() {
+    assert_not_paused();
     let admins = Admins::new();
-    admins.assert_caller_is_admin();
-    let account = runtime::get_named_arg("account");
-    admins.disable(&account);
+    admins.assert_caller_has_role(Role::SuperAdmin);
+    let account = utils::get_identity_arg("account");
+    admins.cancel_nomination(&account);
+    log_admin_action(audit::CANCEL_NOMINATION, Some(account), account);
+}
+
+/// Getter function for the admin list. Returns up to `limit` admins starting with
+/// the `offset`-th one, each with its role flags, which are zero for disabled admins.
+#[no_mangle]
+fn list_admins() {
+    let offset: u32 = runtime::get_named_arg("offset");
+    let limit: u32 = runtime::get_named_arg("limit");
+    let admins = Admins::new().list(offset, limit);
+    runtime::ret(CLValue::from_t(admins).unwrap_or_revert());
+}
+
+/// Getter function for the audit log of the admin actions.
+/// Returns up to `limit` actions starting with the `offset`-th one, oldest first.
+#[no_mangle]
+fn get_audit_log() {
+    let offset: u32 = runtime::get_named_arg("offset");
+    let limit: u32 = runtime::get_named_arg("limit");
+    let entries = AuditLog::new().get(offset, limit);
+    runtime::ret(CLValue::from_t(entries).unwrap_or_revert());
+}
+
+/// Reads the `role` argument, one of the `Role` flags.
+fn get_role_arg() -> Role {
+    let role: u8 = runtime::get_named_arg("role");
+    Role::from_u8(role).unwrap_or_revert_with(ContractError::InvalidRole)
+}
+
+/// Administrator function to grant `role` to `account`.
+/// Accounts without roles are nominated and have to call `accept_admin`.
+/// Stored as a proposal instead if it needs more approvals or the timelock delay is set.
+#[no_mangle]
+fn grant_role() {
+    assert_not_paused();
+    let account = utils::get_identity_arg("account");
+    Proposals::new().submit(AdminAction::GrantRole(account, get_role_arg()));
+}
+
+/// Administrator function to revoke `role` from `account`.
+/// Stored as a proposal instead if it needs more approvals or the timelock delay is set.
+#[no_mangle]
+fn revoke_role() {
+    assert_not_paused();
+    let account = utils::get_identity_arg("account");
+    Proposals::new().submit(AdminAction::RevokeRole(account, get_role_arg()));
 }
 
-/// Adminstrator function to change amount of CSPR to burn when
+/// Adminstrator function to change the fee in motes charged when
 /// calling set_url.
+/// Stored as a proposal instead if it needs more approvals or the timelock delay is set.
+#[no_mangle]
+fn set_registration_fee() {
+    assert_not_paused();
+    let registration_fee: U512 = runtime::get_named_arg("registration_fee");
+    Proposals::new().submit(AdminAction::SetRegistrationFee(registration_fee));
+}
+
+/// Administrator function to change the number of approvals the sensitive admin actions need.
+/// Stored as a proposal instead if it needs more approvals or the timelock delay is set.
+#[no_mangle]
+fn set_approval_threshold() {
+    assert_not_paused();
+    let approval_threshold: u32 = runtime::get_named_arg("approval_threshold");
+    Proposals::new().submit(AdminAction::SetApprovalThreshold(approval_threshold));
+}
+
+/// Administrator function to change the time, in milliseconds, between the approval
+/// of a timelocked proposal and its execution.
+/// Always goes through the timelock, with the current delay.
+#[no_mangle]
+fn set_timelock_delay() {
+    assert_not_paused();
+    let timelock_delay: u64 = runtime::get_named_arg("timelock_delay");
+    Proposals::new().submit(AdminAction::SetTimelockDelay(timelock_delay));
+}
+
+/// Administrator function to propose the `action` sensitive admin action,
+/// with its arguments serialized into `args`. The proposal counts as approved
+/// by the caller. Returns the id of the proposal.
+#[no_mangle]
+fn propose() {
+    assert_not_paused();
+    let action: u8 = runtime::get_named_arg("action");
+    let args: Vec<u8> = runtime::get_named_arg("args");
+    let proposal_id = Proposals::new().propose(AdminAction::from_args(action, &args));
+    runtime::ret(CLValue::from_t(proposal_id).unwrap_or_revert());
+}
+
+/// Administrator function to approve the pending proposal `proposal_id`.
+#[no_mangle]
+fn approve() {
+    assert_not_paused();
+    let proposal_id: u32 = runtime::get_named_arg("proposal_id");
+    Proposals::new().approve(proposal_id);
+}
+
+/// Administrator function to run the action of the proposal `proposal_id`
+/// once it has enough approvals and the timelock delay has passed.
+#[no_mangle]
+fn execute() {
+    assert_not_paused();
+    let proposal_id: u32 = runtime::get_named_arg("proposal_id");
+    Proposals::new().execute(proposal_id);
+}
+
+/// Administrator function to cancel the pending or queued proposal `proposal_id`.
+#[no_mangle]
+fn cancel() {
+    assert_not_paused();
+    let proposal_id: u32 = runtime::get_named_arg("proposal_id");
+    Proposals::new().cancel(proposal_id);
+}
+
+/// Getter function for proposals. Returns the proposal stored under the `proposal_id` argument.
+#[no_mangle]
+fn get_proposal() {
+    let proposal_id: u32 = runtime::get_named_arg("proposal_id");
+    let proposal = Proposals::new()
+        .get(proposal_id)
+        .unwrap_or_revert_with(ContractError::NotFound);
+    runtime::ret(CLValue::from_t(proposal).unwrap_or_revert());
+}
+
+/// Administrator function to enable or disable the HTTPS-only policy
+/// applied to the URLs stored with `set_url` and `set_url_for_account`.
+#[no_mangle]
+fn set_https_only() {
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::SuperAdmin);
+    let https_only: bool = runtime::get_named_arg("https_only");
+    utils::set_key(urls::HTTPS_ONLY, https_only);
+    log_admin_action(audit::SET_HTTPS_ONLY, None, https_only);
+}
+
+/// Administrator function to allow URLs with the `scheme` scheme, validated with `rule`:
+/// `1` for host based URLs, `2` for IPFS content identifiers, `3` for Arweave transaction ids.
+/// Calling it for an already allowed scheme replaces its rule.
 #[no_mangle]
-fn set_cspr_to_burn() {
-    Admins::new().assert_caller_is_admin();
-    let cspr_to_burn: u32 = runtime::get_named_arg("cspr_to_burn");
-    utils::set_key("cspr_to_burn", cspr_to_burn);
+fn allow_scheme() {
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::SuperAdmin);
+    let scheme: String = runtime::get_named_arg("scheme");
+    let rule: u8 = runtime::get_named_arg("rule");
+    Schemes::new().allow(&scheme, rule);
+    log_admin_action(audit::ALLOW_SCHEME, None, (scheme, rule));
+}
+
+/// Administrator function to stop accepting URLs with the `scheme` scheme.
+/// Already stored URLs are not affected.
+#[no_mangle]
+fn disallow_scheme() {
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::SuperAdmin);
+    let scheme: String = runtime::get_named_arg("scheme");
+    Schemes::new().disallow(&scheme);
+    log_admin_action(audit::DISALLOW_SCHEME, None, scheme);
+}
+
+/// Administrator function to reject URLs with the `domain` host, and with the hosts
+/// of its subdomains if `include_subdomains` is set. Already stored URLs are not affected.
+#[no_mangle]
+fn block_domain() {
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::Moderator);
+    let domain: String = runtime::get_named_arg("domain");
+    let include_subdomains: bool = runtime::get_named_arg("include_subdomains");
+    Blocklist::new().block(&domain, include_subdomains);
+    log_admin_action(audit::BLOCK_DOMAIN, None, (domain, include_subdomains));
+}
+
+/// Administrator function to remove `domain` from the blocklist.
+#[no_mangle]
+fn unblock_domain() {
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::Moderator);
+    let domain: String = runtime::get_named_arg("domain");
+    Blocklist::new().unblock(&domain);
+    log_admin_action(audit::UNBLOCK_DOMAIN, None, domain);
+}
+
+/// Administrator function to set the minimum time in milliseconds between two
+/// URL changes of an account with `set_url`. Zero means there is no limit.
+#[no_mangle]
+fn set_min_update_interval() {
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::SuperAdmin);
+    let min_update_interval: u64 = runtime::get_named_arg("min_update_interval");
+    utils::set_key(urls::MIN_UPDATE_INTERVAL, min_update_interval);
+    log_admin_action(audit::SET_MIN_UPDATE_INTERVAL, None, min_update_interval);
+}
+
+/// Administrator function to limit the number of accounts that can store a URL
+/// with the same host. Zero means there is no limit.
+#[no_mangle]
+fn set_max_accounts_per_domain() {
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::SuperAdmin);
+    let max_accounts_per_domain: u32 = runtime::get_named_arg("max_accounts_per_domain");
+    utils::set_key(domains::MAX_ACCOUNTS_PER_DOMAIN, max_accounts_per_domain);
+    log_admin_action(
+        audit::SET_MAX_ACCOUNTS_PER_DOMAIN,
+        None,
+        max_accounts_per_domain,
+    );
+}
+
+/// Administrator function to choose whether registering a URL again
+/// after a deletion charges the registration fee like the first registration.
+#[no_mangle]
+fn set_fee_after_delete() {
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::FeeManager);
+    let fee_after_delete: bool = runtime::get_named_arg("fee_after_delete");
+    utils::set_key(urls::FEE_AFTER_DELETE, fee_after_delete);
+    log_admin_action(audit::SET_FEE_AFTER_DELETE, None, fee_after_delete);
+}
+
+/// Administrator function to set the deposit locked with the first `set_url` call, in motes.
+/// Deposits locked already keep their amount.
+#[no_mangle]
+fn set_deposit_amount() {
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::FeeManager);
+    let deposit_amount: U512 = runtime::get_named_arg("deposit_amount");
+    utils::set_key(deposits::DEPOSIT_AMOUNT, deposit_amount);
+    log_admin_action(audit::SET_DEPOSIT_AMOUNT, None, deposit_amount);
+}
+
+/// Administrator function to stop all state changes, e.g. after a key compromise,
+/// until a super admin calls `unpause`. Getters keep working.
+#[no_mangle]
+fn pause() {
+    if !Admins::new().is_admin(&utils::get_caller()) {
+        runtime::revert(ContractError::PermissionDenied);
+    }
+    utils::set_key(PAUSED, true);
+    log_admin_action(audit::PAUSE, None, ());
+}
+
+/// Administrator function to accept state changes again after `pause`.
+#[no_mangle]
+fn unpause() {
+    Admins::new().assert_caller_has_role(Role::SuperAdmin);
+    utils::set_key(PAUSED, false);
+    log_admin_action(audit::UNPAUSE, None, ());
+}
+
+/// Getter function for the balance of the treasury collecting the registration fees, in motes.
+#[no_mangle]
+fn get_treasury_balance() {
+    let balance = fees::treasury_balance();
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert());
+}
+
+/// Getter function for the balance of the purse holding the locked deposits, in motes.
+#[no_mangle]
+fn get_deposits_balance() {
+    let balance = Deposits::new().balance();
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert());
+}
+
+/// Administrator function to transfer `amount` motes from the treasury
+/// to the `target` account or purse.
+#[no_mangle]
+fn withdraw() {
+    assert_not_paused();
+    Admins::new().assert_caller_has_role(Role::FeeManager);
+    let amount: U512 = runtime::get_named_arg("amount");
+    let target: Key = runtime::get_named_arg("target");
+    fees::withdraw(amount, &target);
+    log_admin_action(audit::WITHDRAW, None, (amount, target));
+}
+
+/// Creates the purses of the contract, converts the named keys stored by earlier versions
+/// and lists the `legacy_admins`, see `Admins::list_legacy_admin`.
+/// Only the installer can call it, right after installing or upgrading the contract.
+#[no_mangle]
+fn migrate() {
+    // Purses are created in the context of the contract, so that only the contract holds them.
+    utils::get_or_create_purse(fees::TREASURY_PURSE);
+    utils::get_or_create_purse(deposits::DEPOSIT_PURSE);
+    fees::migrate_registration_fee();
+
+    let legacy_admins: Vec<Key> = runtime::get_named_arg(admins::LEGACY_ADMINS);
+    let admins = Admins::new();
+    for account in legacy_admins.iter() {
+        admins.list_legacy_admin(account);
+    }
 }
 
-/// Burn tokens.
+/// Guardian function to replace the admins with the `admins` super admins
+/// once there was no admin activity for `recovery::RECOVERY_DELAY`.
+/// Works while the contract is paused, so the new admins can unpause it.
 #[no_mangle]
-fn burn_one_cspr() {
-    let bytes = Vec::from([255u8; 1575]);
-    runtime::ret(CLValue::from_t(bytes).unwrap_or_revert());
+fn recover_admins() {
+    let admins: Vec<Key> = runtime::get_named_arg("admins");
+    recovery::recover(admins);
 }
@@ -0,0 +1,472 @@
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    CLType, CLTyped, Key, URef, U512,
+};
+
+use crate::{
+    admins::{Admins, Role},
+    audit::{self, AuditLog},
+    deposits::Deposits,
+    fees,
+    record::Origin,
+    recovery,
+    urls::Urls,
+    utils, ContractError,
+};
+
+pub const PROPOSALS_DICT: &str = "account-info-proposals";
+pub const PROPOSALS_COUNT: &str = "proposals_count";
+pub const APPROVAL_THRESHOLD: &str = "approval_threshold";
+pub const TIMELOCK_DELAY: &str = "timelock_delay";
+
+/// Waiting for approvals.
+pub const PROPOSAL_PENDING: u8 = 0;
+pub const PROPOSAL_EXECUTED: u8 = 1;
+/// Approved, waiting for the timelock delay to pass.
+pub const PROPOSAL_QUEUED: u8 = 2;
+pub const PROPOSAL_CANCELLED: u8 = 3;
+
+const ADD_ADMIN: u8 = 1;
+const DISABLE_ADMIN: u8 = 2;
+const GRANT_ROLE: u8 = 3;
+const REVOKE_ROLE: u8 = 4;
+const DELETE_URL_FOR_ACCOUNT: u8 = 5;
+const SET_APPROVAL_THRESHOLD: u8 = 7;
+const SET_TIMELOCK_DELAY: u8 = 8;
+/// Same as `DELETE_URL_FOR_ACCOUNT`, and slashes the deposit of the account.
+const DELETE_URL_AND_SLASH_DEPOSIT: u8 = 9;
+const SET_REGISTRATION_FEE: u8 = 10;
+
+/// Sensitive admin actions, which only run after enough admins approved them
+/// and, except for moderation, once the timelock delay has passed.
+/// Serialized as the action kind followed by the serialized arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdminAction {
+    AddAdmin(Key, Option<u64>),
+    DisableAdmin(Key),
+    GrantRole(Key, Role),
+    RevokeRole(Key, Role),
+    /// Deletes the URL of the account, slashing its deposit if set.
+    DeleteUrlForAccount(Key, bool),
+    SetRegistrationFee(U512),
+    SetApprovalThreshold(u32),
+    SetTimelockDelay(u64),
+}
+
+impl AdminAction {
+    /// Parses the action of the `kind` kind out of its serialized arguments.
+    pub fn from_args(kind: u8, args: &[u8]) -> AdminAction {
+        AdminAction::parse(kind, args)
+            .ok()
+            .unwrap_or_revert_with(ContractError::InvalidAction)
+    }
+
+    fn parse(kind: u8, args: &[u8]) -> Result<AdminAction, bytesrepr::Error> {
+        let mut bytes = vec![kind];
+        bytes.extend_from_slice(args);
+        bytesrepr::deserialize(bytes)
+    }
+
+    /// Serialized arguments of the action, without the kind.
+    fn args(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        Ok(self.to_bytes()?.split_off(1))
+    }
+
+    /// Role the proposer and the approvers of the action need.
+    pub fn required_role(&self) -> Role {
+        match self {
+            AdminAction::DeleteUrlForAccount(..) => Role::Moderator,
+            AdminAction::SetRegistrationFee(_) => Role::FeeManager,
+            _ => Role::SuperAdmin,
+        }
+    }
+
+    /// Whether the action is delayed by the timelock. Moderation is not delayed,
+    /// as abusive content has to be removed right away.
+    pub fn is_timelocked(&self) -> bool {
+        !matches!(self, AdminAction::DeleteUrlForAccount(..))
+    }
+
+    /// Account the action is taken on, if any.
+    fn target(&self) -> Option<Key> {
+        match self {
+            AdminAction::AddAdmin(account, _)
+            | AdminAction::DisableAdmin(account)
+            | AdminAction::GrantRole(account, _)
+            | AdminAction::RevokeRole(account, _)
+            | AdminAction::DeleteUrlForAccount(account, _) => Some(*account),
+            _ => None,
+        }
+    }
+
+    fn kind(&self) -> u8 {
+        match self {
+            AdminAction::AddAdmin(..) => ADD_ADMIN,
+            AdminAction::DisableAdmin(_) => DISABLE_ADMIN,
+            AdminAction::GrantRole(..) => GRANT_ROLE,
+            AdminAction::RevokeRole(..) => REVOKE_ROLE,
+            AdminAction::DeleteUrlForAccount(_, false) => DELETE_URL_FOR_ACCOUNT,
+            AdminAction::DeleteUrlForAccount(_, true) => DELETE_URL_AND_SLASH_DEPOSIT,
+            AdminAction::SetRegistrationFee(_) => SET_REGISTRATION_FEE,
+            AdminAction::SetApprovalThreshold(_) => SET_APPROVAL_THRESHOLD,
+            AdminAction::SetTimelockDelay(_) => SET_TIMELOCK_DELAY,
+        }
+    }
+
+    /// Kind of the audit log entry recorded when the action runs.
+    fn audit_kind(&self) -> u8 {
+        match self {
+            AdminAction::AddAdmin(..) => audit::ADD_ADMIN,
+            AdminAction::DisableAdmin(_) => audit::DISABLE_ADMIN,
+            AdminAction::GrantRole(..) => audit::GRANT_ROLE,
+            AdminAction::RevokeRole(..) => audit::REVOKE_ROLE,
+            AdminAction::DeleteUrlForAccount(_, false) => audit::DELETE_URL_FOR_ACCOUNT,
+            AdminAction::DeleteUrlForAccount(_, true) => audit::DELETE_URL_AND_SLASH_DEPOSIT,
+            AdminAction::SetRegistrationFee(_) => audit::SET_REGISTRATION_FEE,
+            AdminAction::SetApprovalThreshold(_) => audit::SET_APPROVAL_THRESHOLD,
+            AdminAction::SetTimelockDelay(_) => audit::SET_TIMELOCK_DELAY,
+        }
+    }
+
+    /// Runs the action on behalf of `proposer`, and records it in the audit log.
+    fn run(&self, proposer: &Key) {
+        let admins = Admins::new();
+        match self {
+            AdminAction::AddAdmin(account, expires_at) => admins.add(account, *expires_at),
+            AdminAction::DisableAdmin(account) => admins.disable(account),
+            AdminAction::GrantRole(account, role) => admins.nominate(account, *role, None),
+            AdminAction::RevokeRole(account, role) => admins.revoke(account, *role),
+            AdminAction::DeleteUrlForAccount(account, slash_deposit) => {
+                Urls::new().delete(account, proposer, Origin::Admin);
+                if *slash_deposit {
+                    Deposits::new().slash(account);
+                }
+            }
+            AdminAction::SetRegistrationFee(fee) => utils::set_key(fees::REGISTRATION_FEE, *fee),
+            AdminAction::SetApprovalThreshold(threshold) => {
+                if *threshold == 0 {
+                    runtime::revert(ContractError::InvalidThreshold);
+                }
+                utils::set_key(APPROVAL_THRESHOLD, *threshold)
+            }
+            AdminAction::SetTimelockDelay(delay) => utils::set_key(TIMELOCK_DELAY, *delay),
+        }
+
+        // There must always be enough super admins to approve further proposals,
+        // even after the expiring grants expire.
+        if admins.permanent_super_admins() < approval_threshold() {
+            runtime::revert(ContractError::InvalidThreshold);
+        }
+
+        // The arguments are logged the same way as they are proposed.
+        let args = self.args().unwrap_or_revert();
+        AuditLog::new().append(proposer, self.audit_kind(), self.target(), args);
+    }
+}
+
+impl ToBytes for AdminAction {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::with_capacity(self.serialized_length());
+        result.append(&mut self.kind().to_bytes()?);
+        match self {
+            AdminAction::AddAdmin(account, expires_at) => {
+                result.append(&mut account.to_bytes()?);
+                result.append(&mut expires_at.to_bytes()?);
+            }
+            AdminAction::DisableAdmin(account) | AdminAction::DeleteUrlForAccount(account, _) => {
+                result.append(&mut account.to_bytes()?)
+            }
+            AdminAction::GrantRole(account, role) | AdminAction::RevokeRole(account, role) => {
+                result.append(&mut account.to_bytes()?);
+                result.append(&mut (*role as u8).to_bytes()?);
+            }
+            AdminAction::SetRegistrationFee(fee) => result.append(&mut fee.to_bytes()?),
+            AdminAction::SetApprovalThreshold(threshold) => {
+                result.append(&mut threshold.to_bytes()?)
+            }
+            AdminAction::SetTimelockDelay(delay) => result.append(&mut delay.to_bytes()?),
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        let args_length = match self {
+            AdminAction::AddAdmin(account, expires_at) => {
+                account.serialized_length() + expires_at.serialized_length()
+            }
+            AdminAction::DisableAdmin(account) | AdminAction::DeleteUrlForAccount(account, _) => {
+                account.serialized_length()
+            }
+            AdminAction::GrantRole(account, role) | AdminAction::RevokeRole(account, role) => {
+                account.serialized_length() + (*role as u8).serialized_length()
+            }
+            AdminAction::SetRegistrationFee(fee) => fee.serialized_length(),
+            AdminAction::SetApprovalThreshold(threshold) => threshold.serialized_length(),
+            AdminAction::SetTimelockDelay(delay) => delay.serialized_length(),
+        };
+        self.kind().serialized_length() + args_length
+    }
+}
+
+impl FromBytes for AdminAction {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (kind, remainder) = u8::from_bytes(bytes)?;
+        match kind {
+            ADD_ADMIN => {
+                let (account, remainder) = utils::identity_from_bytes(remainder)?;
+                let (expires_at, remainder) = Option::<u64>::from_bytes(remainder)?;
+                Ok((AdminAction::AddAdmin(account, expires_at), remainder))
+            }
+            DISABLE_ADMIN | DELETE_URL_FOR_ACCOUNT | DELETE_URL_AND_SLASH_DEPOSIT => {
+                let (account, remainder) = utils::identity_from_bytes(remainder)?;
+                let action = match kind {
+                    DISABLE_ADMIN => AdminAction::DisableAdmin(account),
+                    DELETE_URL_FOR_ACCOUNT => AdminAction::DeleteUrlForAccount(account, false),
+                    _ => AdminAction::DeleteUrlForAccount(account, true),
+                };
+                Ok((action, remainder))
+            }
+            GRANT_ROLE | REVOKE_ROLE => {
+                let (account, remainder) = utils::identity_from_bytes(remainder)?;
+                let (role, remainder) = u8::from_bytes(remainder)?;
+                let role = Role::from_u8(role).ok_or(bytesrepr::Error::Formatting)?;
+                let action = match kind {
+                    GRANT_ROLE => AdminAction::GrantRole(account, role),
+                    _ => AdminAction::RevokeRole(account, role),
+                };
+                Ok((action, remainder))
+            }
+            SET_APPROVAL_THRESHOLD => {
+                let (threshold, remainder) = u32::from_bytes(remainder)?;
+                Ok((AdminAction::SetApprovalThreshold(threshold), remainder))
+            }
+            SET_REGISTRATION_FEE => {
+                let (fee, remainder) = U512::from_bytes(remainder)?;
+                Ok((AdminAction::SetRegistrationFee(fee), remainder))
+            }
+            SET_TIMELOCK_DELAY => {
+                let (delay, remainder) = u64::from_bytes(remainder)?;
+                Ok((AdminAction::SetTimelockDelay(delay), remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// Proposed admin action together with the admins that approved it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proposal {
+    pub action: AdminAction,
+    pub proposed_by: Key,
+    pub approvals: Vec<Key>,
+    pub status: u8,
+    /// Block time from which a queued proposal can be executed, in milliseconds.
+    pub executable_at: u64,
+}
+
+impl Proposal {
+    fn new(action: AdminAction, proposer: Key) -> Proposal {
+        Proposal {
+            action,
+            proposed_by: proposer,
+            approvals: vec![proposer],
+            status: PROPOSAL_PENDING,
+            executable_at: 0,
+        }
+    }
+
+    /// Number of approvals from admins that still have the role the action requires.
+    fn valid_approvals(&self, admins: &Admins) -> u32 {
+        let required_role = self.action.required_role();
+        self.approvals
+            .iter()
+            .filter(|approver| admins.has_role(approver, required_role))
+            .count() as u32
+    }
+
+    /// Queues the proposal once it has enough approvals. Timelocked actions can
+    /// only be executed after the timelock delay has passed.
+    fn queue_if_approved(&mut self, admins: &Admins) {
+        if self.status != PROPOSAL_PENDING || self.valid_approvals(admins) < approval_threshold() {
+            return;
+        }
+        self.status = PROPOSAL_QUEUED;
+        self.executable_at = utils::get_blocktime();
+        if self.action.is_timelocked() {
+            self.executable_at = self.executable_at.saturating_add(timelock_delay());
+        }
+    }
+}
+
+/// Tuple with the same serialization as the proposal: the action kind and its arguments,
+/// then the proposer and the approvers, then the status and the execution time.
+type ProposalTuple = ((u8, Vec<u8>), (Key, Vec<Key>), (u8, u64));
+
+impl CLTyped for Proposal {
+    fn cl_type() -> CLType {
+        ProposalTuple::cl_type()
+    }
+}
+
+impl ToBytes for Proposal {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::with_capacity(self.serialized_length());
+        result.append(&mut self.action.kind().to_bytes()?);
+        result.append(&mut self.action.args()?.to_bytes()?);
+        result.append(&mut self.proposed_by.to_bytes()?);
+        result.append(&mut self.approvals.to_bytes()?);
+        result.append(&mut self.status.to_bytes()?);
+        result.append(&mut self.executable_at.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        // The kind and the arguments of the action, with the length of the arguments.
+        self.action.serialized_length()
+            + U32_SERIALIZED_LENGTH
+            + self.proposed_by.serialized_length()
+            + self.approvals.serialized_length()
+            + self.status.serialized_length()
+            + self.executable_at.serialized_length()
+    }
+}
+
+impl FromBytes for Proposal {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (kind, remainder) = u8::from_bytes(bytes)?;
+        let (args, remainder) = Vec::<u8>::from_bytes(remainder)?;
+        let action = AdminAction::parse(kind, &args)?;
+        let (proposed_by, remainder) = utils::identity_from_bytes(remainder)?;
+        let (approvals, remainder) = Vec::<Key>::from_bytes(remainder)?;
+        let (status, remainder) = u8::from_bytes(remainder)?;
+        let (executable_at, remainder) = u64::from_bytes(remainder)?;
+        let proposal = Proposal {
+            action,
+            proposed_by,
+            approvals,
+            status,
+            executable_at,
+        };
+        Ok((proposal, remainder))
+    }
+}
+
+/// Proposals stored under their sequence number.
+pub struct Proposals {
+    dict_uref: URef,
+}
+
+impl Proposals {
+    pub fn new() -> Proposals {
+        Proposals {
+            dict_uref: utils::get_dictionary(PROPOSALS_DICT),
+        }
+    }
+
+    /// Runs `action` right away if the approval of the caller is enough and the action
+    /// is not delayed by the timelock, otherwise stores it as a proposal approved by the caller.
+    pub fn submit(&self, action: AdminAction) {
+        let admins = Admins::new();
+        admins.assert_caller_has_role(action.required_role());
+        let mut proposal = Proposal::new(action, utils::get_caller());
+        proposal.queue_if_approved(&admins);
+        if proposal.status == PROPOSAL_QUEUED && proposal.executable_at <= utils::get_blocktime() {
+            proposal.action.run(&proposal.proposed_by);
+        } else {
+            self.store(proposal);
+        }
+    }
+
+    /// Stores `action` as a proposal approved by the caller and returns its id.
+    pub fn propose(&self, action: AdminAction) -> u32 {
+        let admins = Admins::new();
+        admins.assert_caller_has_role(action.required_role());
+        let mut proposal = Proposal::new(action, utils::get_caller());
+        proposal.queue_if_approved(&admins);
+        self.store(proposal)
+    }
+
+    pub fn approve(&self, id: u32) {
+        let caller = utils::get_caller();
+        let admins = Admins::new();
+        let mut proposal = self.get_open(id);
+        admins.assert_caller_has_role(proposal.action.required_role());
+        if proposal.approvals.contains(&caller) {
+            runtime::revert(ContractError::AlreadyApproved);
+        }
+        proposal.approvals.push(caller);
+        proposal.queue_if_approved(&admins);
+        storage::dictionary_put(self.dict_uref, &id.to_string(), proposal);
+        recovery::record_admin_activity();
+    }
+
+    pub fn execute(&self, id: u32) {
+        let mut proposal = self.get_open(id);
+        let admins = Admins::new();
+        admins.assert_caller_has_role(proposal.action.required_role());
+
+        // Approvals of admins that lost the role since don't count.
+        if proposal.status != PROPOSAL_QUEUED
+            || proposal.valid_approvals(&admins) < approval_threshold()
+        {
+            runtime::revert(ContractError::NotEnoughApprovals);
+        }
+        if utils::get_blocktime() < proposal.executable_at {
+            runtime::revert(ContractError::TimelockNotExpired);
+        }
+
+        proposal.status = PROPOSAL_EXECUTED;
+        storage::dictionary_put(self.dict_uref, &id.to_string(), proposal.clone());
+        proposal.action.run(&proposal.proposed_by);
+    }
+
+    /// Cancels a pending or queued proposal, so it can't be executed anymore.
+    pub fn cancel(&self, id: u32) {
+        let mut proposal = self.get_open(id);
+        Admins::new().assert_caller_has_role(proposal.action.required_role());
+        proposal.status = PROPOSAL_CANCELLED;
+        storage::dictionary_put(self.dict_uref, &id.to_string(), proposal);
+        recovery::record_admin_activity();
+    }
+
+    pub fn get(&self, id: u32) -> Option<Proposal> {
+        storage::dictionary_get(self.dict_uref, &id.to_string()).unwrap_or_revert()
+    }
+
+    /// Number of proposals made so far, which is also the id of the next one.
+    pub fn count(&self) -> u32 {
+        utils::get_key(PROPOSALS_COUNT).unwrap_or_default()
+    }
+
+    fn store(&self, proposal: Proposal) -> u32 {
+        let id = self.count();
+        storage::dictionary_put(self.dict_uref, &id.to_string(), proposal);
+        utils::set_key(PROPOSALS_COUNT, id + 1);
+        recovery::record_admin_activity();
+        id
+    }
+
+    /// Returns the proposal `id` if it can still be approved and executed.
+    fn get_open(&self, id: u32) -> Proposal {
+        let proposal = self.get(id).unwrap_or_revert_with(ContractError::NotFound);
+        if proposal.status != PROPOSAL_PENDING && proposal.status != PROPOSAL_QUEUED {
+            runtime::revert(ContractError::ProposalNotPending);
+        }
+        proposal
+    }
+}
+
+/// Number of approvals a proposal needs. Contracts installed before proposals
+/// were introduced need one, which runs the actions right away.
+pub fn approval_threshold() -> u32 {
+    utils::get_key(APPROVAL_THRESHOLD).unwrap_or(1)
+}
+
+/// Time between queueing and executing timelocked proposals, in milliseconds.
+pub fn timelock_delay() -> u64 {
+    utils::get_key(TIMELOCK_DELAY).unwrap_or_default()
+}
@@ -0,0 +1,133 @@
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, Key,
+};
+
+use crate::utils;
+
+pub const RECORD_VERSION: u8 = 1;
+pub const CONTENT_HASH_LENGTH: usize = 32;
+
+/// How the URL of the account was last changed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Origin {
+    /// By the account owner, through `set_url` or `delete_url`.
+    Owner = 0,
+    /// By an administrator, through `set_url_for_account` or `delete_url_for_account`.
+    Admin = 1,
+}
+
+impl ToBytes for Origin {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        (*self as u8).serialized_length()
+    }
+}
+
+impl FromBytes for Origin {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (value, remainder) = u8::from_bytes(bytes)?;
+        let origin = match value {
+            0 => Origin::Owner,
+            1 => Origin::Admin,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((origin, remainder))
+    }
+}
+
+/// Account info record stored for every account that has set a URL.
+/// Serialized with a leading version byte, so the layout can be extended later.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountInfoRecord {
+    pub url: String,
+    /// Block time of the last change, in milliseconds.
+    pub updated_at: u64,
+    /// Identity that made the change, an account hash or a contract package hash.
+    pub updated_by: Key,
+    /// Number of changes made to the record, including deletions.
+    pub revision: u32,
+    pub origin: Origin,
+    /// Blake2b-256 hash of the account info file content the owner committed to.
+    pub content_hash: Option<[u8; CONTENT_HASH_LENGTH]>,
+}
+
+impl AccountInfoRecord {
+    /// Record for a URL stored before records were introduced. Nothing is known
+    /// about the change, except that the URL is there.
+    pub fn legacy(address: &Key, url: String) -> AccountInfoRecord {
+        AccountInfoRecord {
+            url,
+            updated_at: 0,
+            updated_by: *address,
+            revision: 0,
+            origin: Origin::Owner,
+            content_hash: None,
+        }
+    }
+}
+
+/// Tuple with the same serialization as the record: the version, the URL and the update time,
+/// then the identity, the revision and the origin, then the content hash.
+type RecordTuple = (
+    (u8, String, u64),
+    (Key, u32, u8),
+    Option<[u8; CONTENT_HASH_LENGTH]>,
+);
+
+impl CLTyped for AccountInfoRecord {
+    fn cl_type() -> CLType {
+        RecordTuple::cl_type()
+    }
+}
+
+impl ToBytes for AccountInfoRecord {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::with_capacity(self.serialized_length());
+        result.append(&mut RECORD_VERSION.to_bytes()?);
+        result.append(&mut self.url.to_bytes()?);
+        result.append(&mut self.updated_at.to_bytes()?);
+        result.append(&mut self.updated_by.to_bytes()?);
+        result.append(&mut self.revision.to_bytes()?);
+        result.append(&mut self.origin.to_bytes()?);
+        result.append(&mut self.content_hash.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        RECORD_VERSION.serialized_length()
+            + self.url.serialized_length()
+            + self.updated_at.serialized_length()
+            + self.updated_by.serialized_length()
+            + self.revision.serialized_length()
+            + self.origin.serialized_length()
+            + self.content_hash.serialized_length()
+    }
+}
+
+impl FromBytes for AccountInfoRecord {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (version, remainder) = u8::from_bytes(bytes)?;
+        if version != RECORD_VERSION {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (url, remainder) = String::from_bytes(remainder)?;
+        let (updated_at, remainder) = u64::from_bytes(remainder)?;
+        let (updated_by, remainder) = utils::identity_from_bytes(remainder)?;
+        let (revision, remainder) = u32::from_bytes(remainder)?;
+        let (origin, remainder) = Origin::from_bytes(remainder)?;
+        let (content_hash, remainder) = Option::<[u8; CONTENT_HASH_LENGTH]>::from_bytes(remainder)?;
+        let record = AccountInfoRecord {
+            url,
+            updated_at,
+            updated_by,
+            revision,
+            origin,
+            content_hash,
+        };
+        Ok((record, remainder))
+    }
+}
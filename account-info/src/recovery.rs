@@ -0,0 +1,54 @@
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{bytesrepr::ToBytes, Key};
+
+use crate::{
+    admins::Admins,
+    audit::{self, AuditLog},
+    proposals::{self, APPROVAL_THRESHOLD},
+    utils, ContractError,
+};
+
+/// Named key of the account or contract package allowed to recover the admins, if any.
+pub const GUARDIAN: &str = "guardian";
+/// Named key of the block time of the last logged admin action or proposal change.
+pub const LAST_ADMIN_ACTIVITY: &str = "last_admin_activity";
+/// Time without admin activity after which the guardian can recover the admins, in milliseconds.
+/// Longer than `NOMINATION_PERIOD`, so no nomination of the previous admins can be accepted.
+pub const RECOVERY_DELAY: u64 = 180 * 24 * 60 * 60 * 1000;
+
+pub fn guardian() -> Option<Key> {
+    utils::get_key::<Option<Key>>(GUARDIAN).flatten()
+}
+
+pub fn record_admin_activity() {
+    utils::set_key(LAST_ADMIN_ACTIVITY, utils::get_blocktime());
+}
+
+/// Block time of the last admin activity, in milliseconds.
+pub fn last_admin_activity() -> u64 {
+    utils::get_key(LAST_ADMIN_ACTIVITY).unwrap_or_default()
+}
+
+/// Replaces the admins with `new_admins` once they have been inactive for `RECOVERY_DELAY`.
+/// Only the guardian can recover the admins.
+pub fn recover(new_admins: Vec<Key>) {
+    let caller = utils::get_caller();
+    if guardian() != Some(caller) {
+        runtime::revert(ContractError::PermissionDenied);
+    }
+    if utils::get_blocktime() < last_admin_activity().saturating_add(RECOVERY_DELAY) {
+        runtime::revert(ContractError::RecoveryNotAvailable);
+    }
+
+    let admins = Admins::new();
+    admins.restore(&new_admins);
+
+    // Make sure the new admins can approve proposals on their own.
+    let permanent_super_admins = admins.permanent_super_admins();
+    if proposals::approval_threshold() > permanent_super_admins {
+        utils::set_key(APPROVAL_THRESHOLD, permanent_super_admins);
+    }
+
+    let args = new_admins.to_bytes().unwrap_or_revert();
+    AuditLog::new().append(&caller, audit::RECOVER_ADMINS, None, args);
+}
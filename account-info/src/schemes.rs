@@ -0,0 +1,124 @@
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::URef;
+
+use crate::{utils, ContractError};
+
+pub const SCHEMES_DICT: &str = "account-info-schemes";
+pub const SCHEME_DISALLOWED: u8 = 0;
+pub const MAX_SCHEME_LENGTH: usize = 32;
+pub const DEFAULT_SCHEMES: [&str; 2] = ["http", "https"];
+
+pub const CIDV0_PREFIX: &str = "Qm";
+pub const CIDV0_LENGTH: usize = 46;
+pub const CIDV1_BASE32_PREFIX: &str = "ba";
+pub const MAX_CIDV1_LENGTH: usize = 128;
+pub const ARWEAVE_TX_ID_LENGTH: usize = 43;
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Validation rules applied to the part of the URL after `<scheme>://`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemeRule {
+    /// A host name or an IP address with an optional port, e.g. `https://example.com:8443`.
+    Host = 1,
+    /// An IPFS content identifier, e.g. `ipfs://bafybei.../casper`.
+    IpfsCid = 2,
+    /// An Arweave transaction id, e.g. `ar://<43 characters base64url id>`.
+    ArweaveTx = 3,
+}
+
+impl SchemeRule {
+    pub fn from_u8(value: u8) -> Option<SchemeRule> {
+        match value {
+            1 => Some(SchemeRule::Host),
+            2 => Some(SchemeRule::IpfsCid),
+            3 => Some(SchemeRule::ArweaveTx),
+            _ => None,
+        }
+    }
+
+    /// Whether `identifier`, the part between `<scheme>://` and the path, is valid.
+    /// Hosts are validated by the URL parser itself, as they can contain a port.
+    pub fn is_valid_identifier(&self, identifier: &str) -> bool {
+        match self {
+            SchemeRule::Host => true,
+            SchemeRule::IpfsCid => is_valid_cid(identifier),
+            SchemeRule::ArweaveTx => is_valid_arweave_tx_id(identifier),
+        }
+    }
+}
+
+/// Admin-managed allowlist of URL schemes, stored as the scheme name to the `SchemeRule` value.
+pub struct Schemes {
+    dict_uref: URef,
+}
+
+impl Schemes {
+    pub fn new() -> Schemes {
+        Schemes {
+            dict_uref: utils::get_dictionary(SCHEMES_DICT),
+        }
+    }
+
+    /// Returns the validation rule of `scheme`, or `None` if the scheme is not allowed.
+    pub fn get(&self, scheme: &str) -> Option<SchemeRule> {
+        let rule: Option<u8> =
+            storage::dictionary_get(self.dict_uref, &scheme.to_ascii_lowercase())
+                .unwrap_or_revert();
+        rule.and_then(SchemeRule::from_u8)
+    }
+
+    pub fn allow(&self, scheme: &str, rule: u8) {
+        if !is_valid_scheme(scheme) {
+            runtime::revert(ContractError::UrlInvalidScheme);
+        }
+        if SchemeRule::from_u8(rule).is_none() {
+            runtime::revert(ContractError::InvalidSchemeRule);
+        }
+        storage::dictionary_put(self.dict_uref, &scheme.to_ascii_lowercase(), rule);
+    }
+
+    pub fn disallow(&self, scheme: &str) {
+        if self.get(scheme).is_none() {
+            runtime::revert(ContractError::UrlSchemeNotAllowed);
+        }
+        storage::dictionary_put(
+            self.dict_uref,
+            &scheme.to_ascii_lowercase(),
+            SCHEME_DISALLOWED,
+        );
+    }
+}
+
+/// Scheme names as defined by RFC 3986: a letter followed by letters, digits, `+`, `-` or `.`.
+pub fn is_valid_scheme(scheme: &str) -> bool {
+    scheme.len() <= MAX_SCHEME_LENGTH
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.')
+}
+
+/// Accepts base58btc encoded CIDv0 and base32 encoded CIDv1 identifiers.
+fn is_valid_cid(cid: &str) -> bool {
+    if cid.starts_with(CIDV0_PREFIX) {
+        cid.len() == CIDV0_LENGTH && cid.bytes().all(|b| BASE58_ALPHABET.contains(&b))
+    } else {
+        cid.starts_with(CIDV1_BASE32_PREFIX)
+            && cid.len() > CIDV1_BASE32_PREFIX.len()
+            && cid.len() <= MAX_CIDV1_LENGTH
+            && cid
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || (b'2'..=b'7').contains(&b))
+    }
+}
+
+/// Arweave transaction ids are 32 bytes encoded as unpadded base64url.
+fn is_valid_arweave_tx_id(id: &str) -> bool {
+    id.len() == ARWEAVE_TX_ID_LENGTH
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
@@ -0,0 +1,265 @@
+use super::ContractError;
+use crate::{
+    idna, punycode,
+    schemes::{self, SchemeRule},
+};
+
+pub const MAX_URL_LENGTH: usize = 256;
+pub const MAX_HOST_LENGTH: usize = 253;
+pub const MAX_LABEL_LENGTH: usize = 63;
+pub const WELL_KNOWN_SEGMENT: &str = ".well-known";
+pub const DEFAULT_PORTS: [(&str, u16); 2] = [("http", 80), ("https", 443)];
+
+/// Parsed representation of an account info URL.
+/// Only the scheme, the host, an optional port and an optional path are supported.
+/// For content-addressed schemes the host is the content identifier.
+#[derive(Debug, PartialEq)]
+pub struct Url<'a> {
+    pub scheme: &'a str,
+    pub rule: SchemeRule,
+    pub host: &'a str,
+    pub port: Option<u16>,
+    pub path: &'a str,
+}
+
+impl<'a> Url<'a> {
+    /// Returns the scheme of `url`, so the validation rule for it can be looked up.
+    pub fn scheme(url: &str) -> Result<&str, ContractError> {
+        let (scheme, _) = url
+            .split_once("://")
+            .ok_or(ContractError::UrlInvalidScheme)?;
+        if schemes::is_valid_scheme(scheme) {
+            Ok(scheme)
+        } else {
+            Err(ContractError::UrlInvalidScheme)
+        }
+    }
+
+    /// Parses and validates `url`, applying `rule` to the part after the scheme.
+    /// Returns the error describing the first problem found.
+    pub fn parse(url: &'a str, rule: SchemeRule) -> Result<Url<'a>, ContractError> {
+        // The limit applies to the canonical URL, internationalized hosts are longer once encoded.
+        if url.chars().count() > MAX_URL_LENGTH {
+            return Err(ContractError::UrlTooLong);
+        }
+        if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(ContractError::UrlInvalidCharacter);
+        }
+
+        let scheme = Url::scheme(url)?;
+        let rest = &url[scheme.len() + "://".len()..];
+
+        // Fragments and queries have no meaning for the account info file location.
+        if rest.contains('#') {
+            return Err(ContractError::UrlHasFragment);
+        }
+        if rest.contains('?') {
+            return Err(ContractError::UrlHasQuery);
+        }
+
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+        if authority.contains('@') {
+            return Err(ContractError::UrlHasUserInfo);
+        }
+
+        let (host, port) = match rule {
+            SchemeRule::Host => {
+                let (host, port) = split_host_and_port(authority)?;
+                validate_host(host)?;
+                (host, port)
+            }
+            _ if authority.is_empty() => return Err(ContractError::UrlMissingHost),
+            _ if !rule.is_valid_identifier(authority) => return Err(ContractError::UrlInvalidHost),
+            _ => (authority, None),
+        };
+        validate_path(path)?;
+
+        Ok(Url {
+            scheme,
+            rule,
+            host,
+            port,
+            path,
+        })
+    }
+
+    /// Returns the canonical form of the URL: lower-cased scheme and host,
+    /// internationalized host labels converted to punycode, without the default port
+    /// and without trailing slashes. Content identifiers are case sensitive and kept as they are.
+    pub fn canonical(&self) -> Result<String, ContractError> {
+        let scheme = self.scheme.to_ascii_lowercase();
+        let host = self.canonical_host()?;
+
+        let mut canonical = format!("{}://{}", scheme, host);
+        if let Some(port) = self.port {
+            let is_default_port = DEFAULT_PORTS.iter().any(|(default_scheme, default_port)| {
+                *default_scheme == scheme && *default_port == port
+            });
+            if !is_default_port {
+                canonical.push_str(&format!(":{}", port));
+            }
+        }
+        canonical.push_str(self.path.trim_end_matches('/'));
+
+        if canonical.len() > MAX_URL_LENGTH {
+            return Err(ContractError::UrlTooLong);
+        }
+        Ok(canonical)
+    }
+
+    /// Returns the host in the form used by the canonical URL.
+    pub fn canonical_host(&self) -> Result<String, ContractError> {
+        match self.rule {
+            SchemeRule::Host => encode_host(self.host),
+            _ => Ok(self.host.to_string()),
+        }
+    }
+}
+
+/// Validates `domain` and returns it in the form used by the canonical URLs.
+pub fn canonical_domain(domain: &str) -> Result<String, ContractError> {
+    validate_host(domain)?;
+    encode_host(domain)
+}
+
+/// Lower-cases the host and converts non-ASCII labels to their `xn--` punycode form.
+/// Labels already in that form must be the canonical encoding of a valid label.
+fn encode_host(host: &str) -> Result<String, ContractError> {
+    let mut labels: Vec<String> = Vec::new();
+    for label in host.split('.') {
+        let label = label.to_lowercase();
+        if label.is_ascii() {
+            if let Some(encoded) = label.strip_prefix(punycode::ACE_PREFIX) {
+                validate_encoded_label(encoded)?;
+            }
+            labels.push(label);
+        } else {
+            let encoded = punycode::encode(&label).ok_or(ContractError::UrlInvalidHost)?;
+            let encoded = format!("{}{}", punycode::ACE_PREFIX, encoded);
+            if encoded.len() > MAX_LABEL_LENGTH {
+                return Err(ContractError::UrlInvalidHost);
+            }
+            labels.push(encoded);
+        }
+    }
+
+    let host = labels.join(".");
+    if host.len() > MAX_HOST_LENGTH {
+        return Err(ContractError::UrlHostTooLong);
+    }
+    Ok(host)
+}
+
+fn validate_encoded_label(encoded: &str) -> Result<(), ContractError> {
+    let decoded = punycode::decode(encoded).ok_or(ContractError::UrlInvalidHost)?;
+    let is_canonical = !decoded.is_ascii()
+        && is_valid_label(&decoded)
+        && decoded == decoded.to_lowercase()
+        && punycode::encode(&decoded).as_deref() == Some(encoded);
+    if is_canonical {
+        Ok(())
+    } else {
+        Err(ContractError::UrlInvalidHost)
+    }
+}
+
+/// Splits the authority part of the URL into the host and the optional port.
+fn split_host_and_port(authority: &str) -> Result<(&str, Option<u16>), ContractError> {
+    let (host, port) = if authority.starts_with('[') {
+        // IPv6 literal, e.g. `[::1]:8080`.
+        let end = authority.find(']').ok_or(ContractError::UrlInvalidHost)?;
+        let (host, rest) = authority.split_at(end + 1);
+        match rest {
+            "" => (host, None),
+            _ => match rest.strip_prefix(':') {
+                Some(port) => (host, Some(port)),
+                None => return Err(ContractError::UrlInvalidHost),
+            },
+        }
+    } else {
+        match authority.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+
+    let port = match port {
+        Some(port) => Some(parse_port(port)?),
+        None => None,
+    };
+    Ok((host, port))
+}
+
+fn parse_port(port: &str) -> Result<u16, ContractError> {
+    if port.is_empty() || port.len() > 5 || !port.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ContractError::UrlInvalidPort);
+    }
+    match port.parse::<u16>() {
+        Ok(0) | Err(_) => Err(ContractError::UrlInvalidPort),
+        Ok(port) => Ok(port),
+    }
+}
+
+fn validate_host(host: &str) -> Result<(), ContractError> {
+    if host.is_empty() {
+        return Err(ContractError::UrlMissingHost);
+    }
+    // Only a sanity check for internationalized hosts, the limit applies to the encoded host.
+    if host.chars().count() > MAX_HOST_LENGTH {
+        return Err(ContractError::UrlHostTooLong);
+    }
+
+    if let Some(address) = host.strip_prefix('[') {
+        let address = address
+            .strip_suffix(']')
+            .ok_or(ContractError::UrlInvalidHost)?;
+        let is_valid_ipv6 = address.contains(':')
+            && address
+                .bytes()
+                .all(|b| b.is_ascii_hexdigit() || b == b':' || b == b'.');
+        return if is_valid_ipv6 {
+            Ok(())
+        } else {
+            Err(ContractError::UrlInvalidHost)
+        };
+    }
+
+    // Domain names and IPv4 addresses are both validated as a list of DNS labels.
+    if host.split('.').all(is_valid_label) {
+        Ok(())
+    } else {
+        Err(ContractError::UrlInvalidHost)
+    }
+}
+
+/// Internationalized labels are allowed here, their encoded length is checked in `canonical_host`.
+/// They must already be in the normalized form browsers resolve, see `idna::is_normalized`.
+fn is_valid_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.chars().count() <= MAX_LABEL_LENGTH
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        && (label.is_ascii() || idna::is_normalized(&label.to_lowercase()))
+}
+
+fn validate_path(path: &str) -> Result<(), ContractError> {
+    if !path.bytes().all(is_path_byte) {
+        return Err(ContractError::UrlInvalidCharacter);
+    }
+    // The `.well-known/casper/account-info.<NETWORK_NAME>.json` part is appended by the readers.
+    if path
+        .split('/')
+        .any(|segment| segment.eq_ignore_ascii_case(WELL_KNOWN_SEGMENT))
+    {
+        return Err(ContractError::UrlHasWellKnownPath);
+    }
+    Ok(())
+}
+
+fn is_path_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"/-._~!$&'()*+,;=:@%".contains(&b)
+}
@@ -1,41 +1,216 @@
-use super::ContractError;
 use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::AccountHash, Key, URef};
+use types::{Key, URef};
+
+use crate::{
+    blocklist::Blocklist,
+    domains::DomainIndex,
+    history::History,
+    record::{AccountInfoRecord, Origin, CONTENT_HASH_LENGTH},
+    schemes::{SchemeRule, Schemes},
+    url::Url,
+    utils, ContractError,
+};
 
 pub const URLS_DICT: &str = "account-info-urls";
+pub const URL_STATUS_DICT: &str = "account-info-url-status";
+pub const RECORDS_DICT: &str = "account-info-records";
+pub const URL_ACTIVE: u8 = 1;
+pub const URL_DELETED: u8 = 2;
+pub const HTTPS_ONLY: &str = "https_only";
+pub const HTTPS_SCHEME: &str = "https";
+pub const FEE_AFTER_DELETE: &str = "fee_after_delete";
+/// Named key of the minimum time between two URL changes of an account, in milliseconds.
+pub const MIN_UPDATE_INTERVAL: &str = "min_update_interval";
 
 pub struct Urls {
     dict_uref: URef,
+    status_dict_uref: URef,
+    records_dict_uref: URef,
 }
 
 impl Urls {
     pub fn new() -> Urls {
-        let dict_key: Key = runtime::get_key(URLS_DICT).unwrap_or_revert();
-        let dict_uref: &URef = dict_key.as_uref().unwrap_or_revert();
         Urls {
-            dict_uref: *dict_uref,
+            dict_uref: utils::get_dictionary(URLS_DICT),
+            status_dict_uref: utils::get_dictionary(URL_STATUS_DICT),
+            records_dict_uref: utils::get_dictionary(RECORDS_DICT),
+        }
+    }
+
+    /// Stores `url` for `address`. `setter` is the identity that made the change.
+    /// `content_hash` is the optional hash of the account info file served under `url`.
+    /// Returns the canonical form of `url` that is stored.
+    pub fn set(
+        &self,
+        address: &Key,
+        url: &str,
+        setter: &Key,
+        origin: Origin,
+        content_hash: Option<[u8; CONTENT_HASH_LENGTH]>,
+    ) -> String {
+        let scheme = Url::scheme(url).unwrap_or_revert();
+        let rule = Schemes::new()
+            .get(scheme)
+            .unwrap_or_revert_with(ContractError::UrlSchemeNotAllowed);
+        let parsed = Url::parse(url, rule).unwrap_or_revert();
+
+        // Contracts upgraded from versions without the policy stay permissive until an admin opts in.
+        // Content-addressed schemes are tamper-evident, so the policy only applies to hosts.
+        let https_only: bool = utils::get_key(HTTPS_ONLY).unwrap_or_default();
+        if https_only && rule == SchemeRule::Host && !scheme.eq_ignore_ascii_case(HTTPS_SCHEME) {
+            runtime::revert(ContractError::InsecureUrlScheme);
+        }
+
+        // Content identifiers are not domains, so they are neither blocked nor indexed.
+        let domain = match rule {
+            SchemeRule::Host => Some(parsed.canonical_host().unwrap_or_revert()),
+            _ => None,
+        };
+        if let Some(domain) = &domain {
+            if Blocklist::new().is_blocked(domain) {
+                runtime::revert(ContractError::DomainBlocked);
+            }
+        }
+
+        let canonical_url = parsed.canonical().unwrap_or_revert();
+        storage::dictionary_put(
+            self.dict_uref,
+            &utils::identity_key(address),
+            canonical_url.clone(),
+        );
+        storage::dictionary_put(
+            self.status_dict_uref,
+            &utils::identity_key(address),
+            URL_ACTIVE,
+        );
+        DomainIndex::new().update(address, domain.as_deref());
+        self.update_record(address, canonical_url.clone(), setter, origin, content_hash);
+        canonical_url
+    }
+
+    /// Marks the URL of `address` as deleted. Dictionary items can't be removed,
+    /// so the URL is cleared and a tombstone is stored in its status.
+    pub fn delete(&self, address: &Key, setter: &Key, origin: Origin) {
+        if self.status(address) != Some(URL_ACTIVE) {
+            runtime::revert(ContractError::NotFound);
         }
+        storage::dictionary_put(self.dict_uref, &utils::identity_key(address), "");
+        storage::dictionary_put(
+            self.status_dict_uref,
+            &utils::identity_key(address),
+            URL_DELETED,
+        );
+        DomainIndex::new().update(address, None);
+        self.update_record(address, String::new(), setter, origin, None);
     }
 
-    pub fn set(&self, address: &AccountHash, url: &str) {
-        if !is_valid_url(url) {
-            runtime::revert(ContractError::BadUrlFormat);
+    pub fn get(&self, address: &Key) -> Option<String> {
+        match self.status(address) {
+            Some(URL_ACTIVE) => {
+                storage::dictionary_get(self.dict_uref, &utils::identity_key(address))
+                    .unwrap_or_revert()
+            }
+            _ => None,
         }
-        storage::dictionary_put(self.dict_uref, &address.to_string(), url);
     }
 
-    pub fn delete(&self, address: &AccountHash) {
-        storage::dictionary_put(self.dict_uref, &address.to_string(), "");
+    /// Returns the record of `address`, or `None` if there is no URL set.
+    pub fn get_record(&self, address: &Key) -> Option<AccountInfoRecord> {
+        let url = self.get(address)?;
+        let record: Option<AccountInfoRecord> =
+            storage::dictionary_get(self.records_dict_uref, &utils::identity_key(address))
+                .unwrap_or_revert();
+        Some(record.unwrap_or_else(|| AccountInfoRecord::legacy(address, url)))
+    }
+
+    /// Returns `URL_ACTIVE`, `URL_DELETED` or `None` if a URL was never set for `address`.
+    pub fn status(&self, address: &Key) -> Option<u8> {
+        let status: Option<u8> =
+            storage::dictionary_get(self.status_dict_uref, &utils::identity_key(address))
+                .unwrap_or_revert();
+        if status.is_some() {
+            return status;
+        }
+
+        // URLs stored before the status dictionary was introduced
+        // were deleted by overwriting them with an empty string.
+        let url: Option<String> =
+            storage::dictionary_get(self.dict_uref, &utils::identity_key(address))
+                .unwrap_or_revert();
+        url.map(|url| {
+            if url.is_empty() {
+                URL_DELETED
+            } else {
+                URL_ACTIVE
+            }
+        })
+    }
+
+    /// Whether setting a URL for `address` requires paying the registration fee.
+    /// Registering for the first time always does, re-registering after a deletion
+    /// only if the `fee_after_delete` policy is enabled.
+    pub fn is_fee_required(&self, address: &Key) -> bool {
+        match self.status(address) {
+            Some(URL_ACTIVE) => false,
+            Some(_) => utils::get_key(FEE_AFTER_DELETE).unwrap_or(true),
+            None => true,
+        }
+    }
+
+    /// Reverts if `address` changed its URL less than `MIN_UPDATE_INTERVAL` ago.
+    /// Changes made by admins don't count, so the owner can correct them right away.
+    /// Contracts upgraded from versions without the limit don't have one until it is set.
+    pub fn assert_update_allowed(&self, address: &Key) {
+        let min_update_interval: u64 = utils::get_key(MIN_UPDATE_INTERVAL).unwrap_or_default();
+        if min_update_interval == 0 {
+            return;
+        }
+        let record: Option<AccountInfoRecord> =
+            storage::dictionary_get(self.records_dict_uref, &utils::identity_key(address))
+                .unwrap_or_revert();
+        if let Some(record) = record.filter(|record| record.origin == Origin::Owner) {
+            if utils::get_blocktime() < record.updated_at.saturating_add(min_update_interval) {
+                runtime::revert(ContractError::TooManyUpdates);
+            }
+        }
     }
 
-    pub fn get(&self, address: &AccountHash) -> Option<String> {
-        storage::dictionary_get(self.dict_uref, &address.to_string()).unwrap_or_revert()
+    fn update_record(
+        &self,
+        address: &Key,
+        url: String,
+        setter: &Key,
+        origin: Origin,
+        content_hash: Option<[u8; CONTENT_HASH_LENGTH]>,
+    ) {
+        let previous: Option<AccountInfoRecord> =
+            storage::dictionary_get(self.records_dict_uref, &utils::identity_key(address))
+                .unwrap_or_revert();
+        let record = AccountInfoRecord {
+            url,
+            updated_at: utils::get_blocktime(),
+            updated_by: *setter,
+            revision: previous.map_or(0, |record| record.revision) + 1,
+            origin,
+            content_hash,
+        };
+        History::new().append(address, &record);
+        storage::dictionary_put(
+            self.records_dict_uref,
+            &utils::identity_key(address),
+            record,
+        );
     }
 }
 
-fn is_valid_url(url: &str) -> bool {
-    url.starts_with("https://") || url.starts_with("http://")
+/// Decodes the hex encoded blake2b-256 hash of an account info file.
+pub fn parse_content_hash(content_hash: &str) -> [u8; CONTENT_HASH_LENGTH] {
+    let mut hash = [0u8; CONTENT_HASH_LENGTH];
+    hex::decode_to_slice(content_hash, &mut hash)
+        .ok()
+        .unwrap_or_revert_with(ContractError::InvalidContentHash);
+    hash
 }
@@ -1,42 +1,137 @@
 use std::convert::TryInto;
 
+use blake2::{
+    digest::{Update, VariableOutput},
+    VarBlake2b,
+};
 use contract::{
-    contract_api::{runtime, storage},
+    contract_api::{runtime, storage, system},
+    ext_ffi,
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{
-    account::AccountHash,
-    bytesrepr::{FromBytes, ToBytes},
+    account::{AccountHash, ACCOUNT_HASH_LENGTH},
+    api_error,
+    bytesrepr::{self, FromBytes, ToBytes},
     system::CallStackElement,
-    CLTyped,
+    ApiError, CLType, CLTyped, Key, URef,
 };
 
 use crate::ContractError;
 
-/// Get AccountHash of the caller. If the caller is StoredContract if fails.
-pub fn get_caller() -> AccountHash {
+/// Prefix of the bytes identifying a contract package, so they can't collide with account hashes.
+const PACKAGE_IDENTITY_PREFIX: &[u8] = b"package-";
+
+/// Get the identity of the caller: the account hash of accounts,
+/// or the contract package hash of stored contracts.
+pub fn get_caller() -> Key {
     let call_stack = runtime::get_call_stack();
-    let caller = call_stack.get(call_stack.len() - 2);
-    element_to_account_hash(caller.unwrap_or_revert())
+    let caller = call_stack
+        .len()
+        .checked_sub(2)
+        .and_then(|index| call_stack.get(index))
+        .unwrap_or_revert_with(ContractError::ReadingCallerError);
+    element_to_identity(caller)
 }
 
-/// Get AccountHash of the deployer at the deployment stage.
-pub fn self_addr() -> AccountHash {
-    element_to_account_hash(runtime::get_call_stack().last().unwrap_or_revert())
+/// Get the identity of the deployer at the deployment stage.
+pub fn self_addr() -> Key {
+    element_to_identity(runtime::get_call_stack().last().unwrap_or_revert())
 }
 
-fn element_to_account_hash(element: &CallStackElement) -> AccountHash {
+fn element_to_identity(element: &CallStackElement) -> Key {
     match element {
-        CallStackElement::Session { account_hash } => *account_hash,
+        CallStackElement::Session { account_hash } => Key::Account(*account_hash),
         CallStackElement::StoredSession {
             account_hash,
             contract_package_hash: _,
             contract_hash: _,
-        } => *account_hash,
+        } => Key::Account(*account_hash),
         CallStackElement::StoredContract {
-            contract_package_hash: _,
+            contract_package_hash,
             contract_hash: _,
-        } => runtime::revert(ContractError::ReadingCallerError),
+        } => Key::from(*contract_package_hash),
+    }
+}
+
+/// Returns the dictionary item key of `identity`. Accounts keep using their account hash,
+/// so the entries stored before contract packages could be callers stay valid.
+pub fn identity_key(identity: &Key) -> String {
+    match identity {
+        Key::Account(account_hash) => account_hash.to_string(),
+        _ => dictionary_key(&[&identity_bytes(identity)]),
+    }
+}
+
+/// Returns the bytes identifying `identity` in hashed dictionary item keys.
+pub fn identity_bytes(identity: &Key) -> Vec<u8> {
+    match identity {
+        Key::Account(account_hash) => account_hash.value().to_vec(),
+        Key::Hash(package_hash) => [PACKAGE_IDENTITY_PREFIX, package_hash].concat(),
+        _ => runtime::revert(ContractError::InvalidIdentity),
+    }
+}
+
+/// Deserializes an identity, either an account hash or a contract package hash `Key`.
+pub fn identity_from_bytes(bytes: &[u8]) -> Result<(Key, &[u8]), bytesrepr::Error> {
+    let (identity, remainder) = Key::from_bytes(bytes)?;
+    match identity {
+        Key::Account(_) | Key::Hash(_) => Ok((identity, remainder)),
+        _ => Err(bytesrepr::Error::Formatting),
+    }
+}
+
+/// Identity passed as a whole argument, which is either a `Key` or, from clients written
+/// before contract packages could be callers, an account hash.
+struct IdentityArg(Key);
+
+impl CLTyped for IdentityArg {
+    fn cl_type() -> CLType {
+        Key::cl_type()
+    }
+}
+
+impl FromBytes for IdentityArg {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        if bytes.len() == ACCOUNT_HASH_LENGTH {
+            let (account_hash, remainder) = AccountHash::from_bytes(bytes)?;
+            return Ok((IdentityArg(Key::Account(account_hash)), remainder));
+        }
+        let (identity, remainder) = identity_from_bytes(bytes)?;
+        Ok((IdentityArg(identity), remainder))
+    }
+}
+
+/// Returns the identity passed as the `name` argument, as a `Key` or an account hash.
+pub fn get_identity_arg(name: &str) -> Key {
+    let identity: IdentityArg = runtime::get_named_arg(name);
+    identity.0
+}
+
+/// Returns the identity stored under `key` in the `dict_uref` dictionary.
+pub fn get_identity(dict_uref: URef, key: &str) -> Option<Key> {
+    storage::dictionary_get(dict_uref, key).unwrap_or_revert()
+}
+
+/// Returns the block time of the current block, in milliseconds.
+pub fn get_blocktime() -> u64 {
+    u64::from(runtime::get_blocktime())
+}
+
+/// Returns the named argument `name`, or `None` if the caller didn't pass it.
+pub fn get_optional_named_arg<T: FromBytes + CLTyped>(name: &str) -> Option<T> {
+    let mut arg_size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(
+            name.as_bytes().as_ptr(),
+            name.len(),
+            &mut arg_size as *mut usize,
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(()) => Some(runtime::get_named_arg(name)),
+        Err(ApiError::MissingArgument) => None,
+        Err(error) => runtime::revert(error),
     }
 }
 
@@ -64,3 +159,49 @@ pub fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
         }
     }
 }
+
+/// Returns the seed URef of the dictionary stored under `name` in the current context.
+pub fn get_dictionary(name: &str) -> URef {
+    let dict_key = runtime::get_key(name).unwrap_or_revert();
+    *dict_key.as_uref().unwrap_or_revert()
+}
+
+/// Returns the seed URef of the dictionary `name` created by an earlier installation
+/// or upgrade, or creates a new dictionary if there is none.
+pub fn get_or_create_dictionary(name: &str) -> URef {
+    match runtime::get_key(name) {
+        Some(dict_key) => *dict_key.as_uref().unwrap_or_revert(),
+        None => storage::new_dictionary(name).unwrap_or_revert(),
+    }
+}
+
+/// Returns the purse stored under `name` in the current context.
+pub fn get_purse(name: &str) -> URef {
+    let purse_key = runtime::get_key(name).unwrap_or_revert();
+    *purse_key.as_uref().unwrap_or_revert()
+}
+
+/// Returns the purse `name` created by an earlier installation or upgrade,
+/// or creates a new purse if there is none.
+pub fn get_or_create_purse(name: &str) -> URef {
+    match runtime::get_key(name) {
+        Some(purse_key) => *purse_key.as_uref().unwrap_or_revert(),
+        None => {
+            let purse = system::create_purse();
+            runtime::put_key(name, purse.into());
+            purse
+        }
+    }
+}
+
+/// Builds a dictionary item key out of `parts`. Dictionary item keys are limited to 64 bytes,
+/// so the parts are hashed with blake2b-256 and the hex encoded digest is returned.
+pub fn dictionary_key(parts: &[&[u8]]) -> String {
+    let mut hasher = VarBlake2b::new(32).ok().unwrap_or_revert();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut digest = [0u8; 32];
+    hasher.finalize_variable(|hash| digest.copy_from_slice(hash));
+    hex::encode(digest)
+}
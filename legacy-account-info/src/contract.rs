@@ -0,0 +1,60 @@
+#![no_main]
+
+//! Installs a contract with the named keys of the first release of `account-info`,
+//! so that the tests can upgrade it to the current version. The contract has no
+//! entry points, as the upgrade replaces them.
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{contracts::NamedKeys, EntryPoints};
+
+const NAME: &str = "account-info";
+
+#[no_mangle]
+fn call() {
+    let cspr_to_burn: u32 = runtime::get_named_arg("cspr_to_burn");
+
+    let (contract_package_hash, access_token) = storage::create_contract_package_at_hash();
+    runtime::put_key(&format!("{}-package", NAME), contract_package_hash.into());
+    runtime::put_key(
+        &format!("{}-package-access-uref", NAME),
+        access_token.into(),
+    );
+    runtime::put_key(
+        &format!("{}-package-hash", NAME),
+        storage::new_uref(contract_package_hash).into(),
+    );
+
+    // The deployer is the only admin, stored under its account hash.
+    let mut named_keys = NamedKeys::new();
+    let admins_dict = storage::new_dictionary("account-info-admins").unwrap_or_revert();
+    storage::dictionary_put(admins_dict, &runtime::get_caller().to_string(), true);
+    named_keys.insert("account-info-admins".to_string(), admins_dict.into());
+    named_keys.insert("admins_count".to_string(), storage::new_uref(1u32).into());
+
+    let urls_dict = storage::new_dictionary("account-info-urls").unwrap_or_revert();
+    named_keys.insert("account-info-urls".to_string(), urls_dict.into());
+
+    // The fee in whole CSPR, which the upgrade converts into motes.
+    named_keys.insert(
+        "cspr_to_burn".to_string(),
+        storage::new_uref(cspr_to_burn).into(),
+    );
+    named_keys.insert(
+        "package_hash".to_string(),
+        storage::new_uref(contract_package_hash).into(),
+    );
+
+    let (contract_hash, _) =
+        storage::add_contract_version(contract_package_hash, EntryPoints::new(), named_keys);
+    runtime::put_key(
+        &format!("{}-latest-version-contract", NAME),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}-latest-version-contract-hash", NAME),
+        storage::new_uref(contract_hash).into(),
+    );
+}
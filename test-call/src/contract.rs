@@ -0,0 +1,52 @@
+#![no_main]
+
+//! Calls an entry point of a stored contract and stores the returned value under the
+//! `result` named key of the caller, so that the tests can read the values returned
+//! by the getters of the account info contract.
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    contracts::ContractHash,
+    CLType, CLTyped, RuntimeArgs,
+};
+
+/// Value returned by the entry point, kept as its serialized bytes.
+struct ReturnedValue(Vec<u8>);
+
+impl CLTyped for ReturnedValue {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ReturnedValue {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        Ok(self.0.clone())
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl FromBytes for ReturnedValue {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        Ok((ReturnedValue(bytes.to_vec()), &[]))
+    }
+}
+
+#[no_mangle]
+fn call() {
+    let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
+    let entry_point: String = runtime::get_named_arg("entry_point");
+    // The arguments of the entry point, serialized as `RuntimeArgs`.
+    let args: Vec<u8> = runtime::get_named_arg("args");
+    let args: RuntimeArgs = bytesrepr::deserialize(args).unwrap_or_revert();
+
+    let result: ReturnedValue = runtime::call_contract(contract_hash, &entry_point, args);
+    runtime::put_key("result", storage::new_uref(result).into());
+}
@@ -74,7 +74,7 @@ BASE_URL=$(casper-client get-dictionary-item \
 
 CHAIN_NAME=$(curl -s http://$NODE_ADDRESS:8888/status | jq -r '.chainspec_name')
 
-if [ "$BASE_URL" = "null" ]; then
+if [ "$BASE_URL" = "null" ] || [ -z "$BASE_URL" ]; then
   echo "Account information URL is not set for the given public key on the $CHAIN_NAME network"
   exit 0
 else